@@ -8,11 +8,12 @@ use orderwal::{
 
 use core::{
   mem,
-  ops::Bound,
+  ops::{Bound, RangeBounds},
   sync::atomic::{AtomicU64, Ordering},
 };
 use std::sync::Arc;
 
+use super::bloom::BloomFilter;
 use super::types::{entry_ref::EntryRef, key::Key, meta::Meta, query::Query};
 
 /// The reader of the active log file.
@@ -45,20 +46,327 @@ impl<C, S> ActiveLogFileReader<C, S>
 where
   C: StaticComparator,
 {
-  /// Returns `true` if the active log contains the key.
+  /// Returns a positioned cursor for scanning the active log in key order at
+  /// `version`. See [`TableScanIter`] for how it merges MVCC versions of the
+  /// same key and how direction reversal works.
   #[inline]
-  pub fn contains_key(&self, version: u64, key: &[u8]) -> bool {
+  pub fn scan(&self, version: u64, now: u64) -> TableScanIter<'_, C, S> {
+    TableScanIter::new(self, version, now)
+  }
+
+  /// Returns a double-ended iterator over every distinct user key in `range`,
+  /// as of `version`.
+  ///
+  /// Like [`get`](Self::get), among all versions of a key only the newest
+  /// one `<= version` is ever visible; a key whose newest visible version is
+  /// a tombstone, or, under the `ttl` feature, has expired as of `now`, is
+  /// skipped entirely rather than yielded. The front and back of the
+  /// iterator are independent [`lower_bound`](Self::lower_bound)/
+  /// [`upper_bound`](Self::upper_bound) walks that stop as soon as they
+  /// would cross each other, so [`next`](Iterator::next) and
+  /// [`next_back`](DoubleEndedIterator::next_back) can be interleaved
+  /// freely to page from either end.
+  #[inline]
+  pub fn range(&self, version: u64, range: impl RangeBounds<[u8]>, now: u64) -> RangeIter<'_, C, S> {
+    RangeIter::new(self, version, range, now)
+  }
+
+  /// Captures a [`Snapshot`] pinned to the current `max_version`.
+  ///
+  /// The snapshot is registered with the owning [`SnapshotList`] until it is
+  /// dropped, so compaction/GC can observe its version via
+  /// `SnapshotList::oldest` and avoid reclaiming data it still needs.
+  #[inline]
+  pub fn snapshot(&self, now: u64) -> Snapshot<C, S> {
+    let version = self.max_version();
+    self.0.snapshots.acquire(version);
+    Snapshot {
+      reader: ActiveLogFileReader(self.0.clone()),
+      version,
+      now,
+    }
+  }
+}
+
+impl<C, S> Clone for ActiveLogFileReader<C, S> {
+  #[inline]
+  fn clone(&self) -> Self {
+    Self(self.0.clone())
+  }
+}
+
+/// Which way a [`TableScanIter`] is currently moving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ScanDirection {
+  Forward,
+  Backward,
+}
+
+/// A LevelDB-style positioned cursor over the active log at a pinned
+/// `version`, applying the same MVCC rule as [`ActiveLogFileReader::get`]:
+/// among every version of a user key, only the newest version `<= version`
+/// is ever visible, so each [`advance`](Self::advance)/[`retreat`](Self::retreat)
+/// call moves by one distinct user key, not one physical entry.
+///
+/// This is the active log's half of a table scan: it is deliberately built
+/// as a single positioned cursor (seek + step one key at a time) so that,
+/// once frozen logs expose a comparable cursor, a table scan can be built by
+/// feeding several `TableScanIter`s into a key-ordered min/max-heap and
+/// always stepping whichever cursor is currently smallest (forward) or
+/// largest (backward). Reversing direction mid-scan needs no special
+/// bookkeeping: `advance`/`retreat` always re-seed their lookup at the
+/// cursor's current key, so switching which one you call is enough.
+pub struct TableScanIter<'a, C, S> {
+  reader: &'a ActiveLogFileReader<C, S>,
+  version: u64,
+  now: u64,
+  current: Option<EntryRef<'a, C>>,
+  direction: ScanDirection,
+}
+
+impl<'a, C, S> TableScanIter<'a, C, S>
+where
+  C: StaticComparator,
+{
+  #[inline]
+  fn new(reader: &'a ActiveLogFileReader<C, S>, version: u64, now: u64) -> Self {
+    Self {
+      reader,
+      version,
+      now,
+      current: None,
+      direction: ScanDirection::Forward,
+    }
+  }
+
+  /// Returns `true` if the cursor is positioned on an entry.
+  #[inline]
+  pub fn valid(&self) -> bool {
+    self.current.is_some()
+  }
+
+  /// Returns the entry the cursor is currently positioned on, if any.
+  #[inline]
+  pub fn current(&self) -> Option<&EntryRef<'a, C>> {
+    self.current.as_ref()
+  }
+
+  /// Positions the cursor on the first key in the log, in ascending order.
+  #[inline]
+  pub fn seek_to_first(&mut self) {
+    self.direction = ScanDirection::Forward;
+    self.current = self.reader.first(self.version, self.now);
+  }
+
+  /// Positions the cursor on the last key in the log, in descending order.
+  #[inline]
+  pub fn seek_to_last(&mut self) {
+    self.direction = ScanDirection::Backward;
+    self.current = self.reader.last(self.version, self.now);
+  }
+
+  /// Positions the cursor on the first key `>= key`, in ascending order.
+  #[inline]
+  pub fn seek(&mut self, key: &[u8]) {
+    self.direction = ScanDirection::Forward;
+    self.current = self
+      .reader
+      .lower_bound(self.version, Bound::Included(key), self.now);
+  }
+
+  /// Moves the cursor to the next key in ascending order. Returns `true` if
+  /// the cursor landed on an entry.
+  pub fn advance(&mut self) -> bool {
+    self.current = match self.current.take() {
+      Some(entry) => self
+        .reader
+        .lower_bound(self.version, Bound::Excluded(entry.key()), self.now),
+      None => self.reader.first(self.version, self.now),
+    };
+    self.direction = ScanDirection::Forward;
+    self.current.is_some()
+  }
+
+  /// Moves the cursor to the previous key in descending order. Returns
+  /// `true` if the cursor landed on an entry.
+  pub fn retreat(&mut self) -> bool {
+    self.current = match self.current.take() {
+      Some(entry) => self
+        .reader
+        .upper_bound(self.version, Bound::Excluded(entry.key()), self.now),
+      None => self.reader.last(self.version, self.now),
+    };
+    self.direction = ScanDirection::Backward;
+    self.current.is_some()
+  }
+}
+
+/// A double-ended iterator over a key range in the active log at a pinned
+/// `version`, returned by [`ActiveLogFileReader::range`].
+///
+/// Unlike [`TableScanIter`], which is a positioned cursor you seek and step,
+/// this is a plain [`Iterator`]/[`DoubleEndedIterator`] bounded to a range:
+/// each [`next`](Iterator::next)/[`next_back`](DoubleEndedIterator::next_back)
+/// call narrows its own end of the range by one key, so the two ends can be
+/// driven independently until they meet.
+pub struct RangeIter<'a, C, S> {
+  reader: &'a ActiveLogFileReader<C, S>,
+  version: u64,
+  now: u64,
+  lower: Bound<std::vec::Vec<u8>>,
+  upper: Bound<std::vec::Vec<u8>>,
+  done: bool,
+}
+
+impl<'a, C, S> RangeIter<'a, C, S> {
+  fn new(
+    reader: &'a ActiveLogFileReader<C, S>,
+    version: u64,
+    range: impl RangeBounds<[u8]>,
+    now: u64,
+  ) -> Self {
+    Self {
+      reader,
+      version,
+      now,
+      lower: to_owned_bound(range.start_bound()),
+      upper: to_owned_bound(range.end_bound()),
+      done: false,
+    }
+  }
+}
+
+#[inline]
+fn to_owned_bound(bound: Bound<&[u8]>) -> Bound<std::vec::Vec<u8>> {
+  match bound {
+    Bound::Included(b) => Bound::Included(b.to_vec()),
+    Bound::Excluded(b) => Bound::Excluded(b.to_vec()),
+    Bound::Unbounded => Bound::Unbounded,
+  }
+}
+
+#[inline]
+fn as_bound(bound: &Bound<std::vec::Vec<u8>>) -> Bound<&[u8]> {
+  match bound {
+    Bound::Included(b) => Bound::Included(b.as_slice()),
+    Bound::Excluded(b) => Bound::Excluded(b.as_slice()),
+    Bound::Unbounded => Bound::Unbounded,
+  }
+}
+
+impl<'a, C, S> Iterator for RangeIter<'a, C, S>
+where
+  C: StaticComparator,
+{
+  type Item = EntryRef<'a, C>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+
+    let entry = match self
+      .reader
+      .lower_bound(self.version, as_bound(&self.lower), self.now)
+    {
+      Some(entry) => entry,
+      None => {
+        self.done = true;
+        return None;
+      }
+    };
+
+    let within_upper = match &self.upper {
+      Bound::Unbounded => true,
+      Bound::Included(b) => C::compare(entry.key(), b) != core::cmp::Ordering::Greater,
+      Bound::Excluded(b) => C::compare(entry.key(), b) == core::cmp::Ordering::Less,
+    };
+    if !within_upper {
+      self.done = true;
+      return None;
+    }
+
+    self.lower = Bound::Excluded(entry.key().to_vec());
+    Some(entry)
+  }
+}
+
+impl<'a, C, S> DoubleEndedIterator for RangeIter<'a, C, S>
+where
+  C: StaticComparator,
+{
+  fn next_back(&mut self) -> Option<Self::Item> {
+    if self.done {
+      return None;
+    }
+
+    let entry = match self
+      .reader
+      .upper_bound(self.version, as_bound(&self.upper), self.now)
+    {
+      Some(entry) => entry,
+      None => {
+        self.done = true;
+        return None;
+      }
+    };
+
+    let within_lower = match &self.lower {
+      Bound::Unbounded => true,
+      Bound::Included(b) => C::compare(entry.key(), b) != core::cmp::Ordering::Less,
+      Bound::Excluded(b) => C::compare(entry.key(), b) == core::cmp::Ordering::Greater,
+    };
+    if !within_lower {
+      self.done = true;
+      return None;
+    }
+
+    self.upper = Bound::Excluded(entry.key().to_vec());
+    Some(entry)
+  }
+}
+
+impl<C, S> ActiveLogFileReader<C, S>
+where
+  C: StaticComparator,
+{
+  /// Tests the attached Bloom filter, if any. Returns `true` (may be
+  /// present) when there is no filter, so callers that skip this check are
+  /// never wrong, only potentially slower.
+  #[inline]
+  fn may_contain_key(&self, key: &[u8]) -> bool {
+    match &*self.0.filter.lock().unwrap() {
+      Some(filter) => filter.may_contain(key),
+      None => true,
+    }
+  }
+
+  /// Returns `true` if the active log contains the key and, under the
+  /// `ttl` feature, it has not expired as of `now`.
+  ///
+  /// A tombstoned key (see [`WriteBatch::delete`]) counts as not contained,
+  /// even though its entry is still physically present in the log.
+  #[inline]
+  pub fn contains_key(&self, version: u64, key: &[u8], now: u64) -> bool {
     if !self.contains_version(version) {
       return false;
     }
 
+    if !self.may_contain_key(key) {
+      return false;
+    }
+
     let mut ent = self
       .0
       .lower_bound(Bound::Included(Query::new(Meta::query(Meta::MAX_VERSION), key)).as_ref());
 
     while let Some(e) = ent {
       if e.key().version() <= version {
-        return true;
+        #[cfg(feature = "ttl")]
+        if is_expired(e.key().expire_at(), now) {
+          return false;
+        }
+        return !e.key().is_tombstone();
       }
 
       ent = e.next();
@@ -68,118 +376,345 @@ where
   }
 
   /// Get the entry by the key and version.
+  ///
+  /// Returns `None` if the newest version of `key` visible at `version` is a
+  /// tombstone (see [`WriteBatch::delete`]) or, under the `ttl` feature, has
+  /// expired as of `now`.
   #[inline]
-  pub fn get<'a>(&'a self, version: u64, key: &[u8]) -> Option<EntryRef<'a, C>> {
-    self
-      .contains_version(version)
-      .then(|| {
-        let mut ent = self
-          .0
-          .lower_bound(Bound::Included(Query::new(Meta::query(Meta::MAX_VERSION), key)).as_ref());
-
-        while let Some(e) = ent {
-          if e.key().version() <= version {
-            return Some(EntryRef::new(e));
-          }
-
-          ent = e.next();
+  pub fn get<'a>(
+    &'a self,
+    version: u64,
+    key: &[u8],
+    now: u64,
+  ) -> Option<EntryRef<'a, C>> {
+    if !self.contains_version(version) || !self.may_contain_key(key) {
+      return None;
+    }
+
+    let mut ent = self
+      .0
+      .lower_bound(Bound::Included(Query::new(Meta::query(Meta::MAX_VERSION), key)).as_ref());
+
+    while let Some(e) = ent {
+      if e.key().version() <= version {
+        #[cfg(feature = "ttl")]
+        if is_expired(e.key().expire_at(), now) {
+          return None;
         }
+        return (!e.key().is_tombstone()).then(|| EntryRef::new(e));
+      }
 
-        None
-      })
-      .flatten()
+      ent = e.next();
+    }
+
+    None
   }
 
   /// Returns the first entry in the active log.
+  ///
+  /// A key whose newest visible version is a tombstone, or, under the `ttl`
+  /// feature, has expired as of `now`, is skipped in favor of the next
+  /// smallest key, so this never surfaces a deleted or expired key.
   #[inline]
-  pub fn first(&self, version: u64) -> Option<EntryRef<'_, C>> {
-    self
-      .contains_version(version)
-      .then(|| {
-        let mut first = self.0.first();
+  pub fn first(&self, version: u64, now: u64) -> Option<EntryRef<'_, C>> {
+    if !self.contains_version(version) {
+      return None;
+    }
 
-        while let Some(ent) = first {
-          if ent.key().version() <= version {
-            return Some(EntryRef::new(ent));
-          }
+    let mut first = self.0.first();
 
-          first = ent.next();
+    loop {
+      let mut ent = first;
+      let mut visible = None;
+
+      while let Some(e) = ent {
+        if e.key().version() <= version {
+          visible = Some(e);
+          break;
         }
 
-        None
-      })
-      .flatten()
+        ent = e.next();
+      }
+
+      let entry = visible?;
+      #[cfg(feature = "ttl")]
+      let expired = is_expired(entry.key().expire_at(), now);
+      #[cfg(not(feature = "ttl"))]
+      let expired = false;
+      if !entry.key().is_tombstone() && !expired {
+        return Some(EntryRef::new(entry));
+      }
+
+      first = self.0.lower_bound(Bound::Excluded(
+        Query::new(Meta::query(Meta::MAX_VERSION), entry.key().key()).as_ref(),
+      ));
+    }
   }
 
   /// Returns the last entry in the active log.
+  ///
+  /// A key whose newest visible version is a tombstone, or, under the `ttl`
+  /// feature, has expired as of `now`, is skipped in favor of the next
+  /// largest key, so this never surfaces a deleted or expired key.
   #[inline]
-  pub fn last(&self, version: u64) -> Option<EntryRef<'_, C>> {
-    self
-      .contains_version(version)
-      .then(|| {
-        let mut last = self.0.last();
+  pub fn last(&self, version: u64, now: u64) -> Option<EntryRef<'_, C>> {
+    if !self.contains_version(version) {
+      return None;
+    }
 
-        while let Some(ent) = last {
-          if ent.key().version() <= version {
-            return Some(EntryRef::new(ent));
-          }
+    let mut last = self.0.last();
 
-          last = ent.prev();
+    loop {
+      let mut ent = last;
+      let mut visible = None;
+
+      while let Some(e) = ent {
+        if e.key().version() <= version {
+          visible = Some(e);
+          break;
         }
 
-        None
-      })
-      .flatten()
+        ent = e.prev();
+      }
+
+      let entry = visible?;
+      #[cfg(feature = "ttl")]
+      let expired = is_expired(entry.key().expire_at(), now);
+      #[cfg(not(feature = "ttl"))]
+      let expired = false;
+      if !entry.key().is_tombstone() && !expired {
+        return Some(EntryRef::new(entry));
+      }
+
+      last = self.0.upper_bound(Bound::Excluded(
+        Query::new(Meta::query(Meta::MAX_VERSION), entry.key().key()).as_ref(),
+      ));
+    }
   }
 
   /// Returns a value associated to the highest element whose key is below the given bound. If no such element is found then `None` is returned.
+  ///
+  /// A key whose newest visible version is a tombstone, or, under the `ttl`
+  /// feature, has expired as of `now`, is skipped in favor of the next
+  /// largest key below the bound.
   #[inline]
-  pub fn upper_bound(&self, version: u64, bound: Bound<&[u8]>) -> Option<EntryRef<'_, C>> {
-    self
-      .contains_version(version)
-      .then(|| {
-        let mut upper_bound = self.0.upper_bound(
-          bound
-            .map(|b| Query::new(Meta::query(Meta::MAX_VERSION), b))
-            .as_ref(),
-        );
-
-        while let Some(ent) = upper_bound {
-          if ent.key().version() <= version {
-            return Some(EntryRef::new(ent));
-          }
-
-          upper_bound = ent.next();
+  pub fn upper_bound(
+    &self,
+    version: u64,
+    bound: Bound<&[u8]>,
+    now: u64,
+  ) -> Option<EntryRef<'_, C>> {
+    if !self.contains_version(version) {
+      return None;
+    }
+
+    let mut upper_bound = self.0.upper_bound(
+      bound
+        .map(|b| Query::new(Meta::query(Meta::MAX_VERSION), b))
+        .as_ref(),
+    );
+
+    loop {
+      let mut ent = upper_bound;
+      let mut visible = None;
+
+      while let Some(e) = ent {
+        if e.key().version() <= version {
+          visible = Some(e);
+          break;
         }
 
-        None
-      })
-      .flatten()
+        ent = e.next();
+      }
+
+      let entry = visible?;
+      #[cfg(feature = "ttl")]
+      let expired = is_expired(entry.key().expire_at(), now);
+      #[cfg(not(feature = "ttl"))]
+      let expired = false;
+      if !entry.key().is_tombstone() && !expired {
+        return Some(EntryRef::new(entry));
+      }
+
+      upper_bound = self.0.upper_bound(Bound::Excluded(
+        Query::new(Meta::query(Meta::MAX_VERSION), entry.key().key()).as_ref(),
+      ));
+    }
   }
 
   /// Returns a value associated to the lowest element whose key is above the given bound. If no such element is found then `None` is returned.
+  ///
+  /// A key whose newest visible version is a tombstone, or, under the `ttl`
+  /// feature, has expired as of `now`, is skipped in favor of the next
+  /// smallest key above the bound.
   #[inline]
-  pub fn lower_bound(&self, version: u64, bound: Bound<&[u8]>) -> Option<EntryRef<'_, C>> {
-    self
-      .contains_version(version)
-      .then(|| {
-        let mut lower_bound = self.0.lower_bound(
-          bound
-            .map(|b| Query::new(Meta::query(Meta::MAX_VERSION), b))
-            .as_ref(),
-        );
-
-        while let Some(ent) = lower_bound {
-          if ent.key().version() <= version {
-            return Some(EntryRef::new(ent));
-          }
-
-          lower_bound = ent.next();
+  pub fn lower_bound(
+    &self,
+    version: u64,
+    bound: Bound<&[u8]>,
+    now: u64,
+  ) -> Option<EntryRef<'_, C>> {
+    if !self.contains_version(version) {
+      return None;
+    }
+
+    let mut lower_bound = self.0.lower_bound(
+      bound
+        .map(|b| Query::new(Meta::query(Meta::MAX_VERSION), b))
+        .as_ref(),
+    );
+
+    loop {
+      let mut ent = lower_bound;
+      let mut visible = None;
+
+      while let Some(e) = ent {
+        if e.key().version() <= version {
+          visible = Some(e);
+          break;
         }
 
-        None
-      })
-      .flatten()
+        ent = e.next();
+      }
+
+      let entry = visible?;
+      #[cfg(feature = "ttl")]
+      let expired = is_expired(entry.key().expire_at(), now);
+      #[cfg(not(feature = "ttl"))]
+      let expired = false;
+      if !entry.key().is_tombstone() && !expired {
+        return Some(EntryRef::new(entry));
+      }
+
+      lower_bound = self.0.lower_bound(Bound::Excluded(
+        Query::new(Meta::query(Meta::MAX_VERSION), entry.key().key()).as_ref(),
+      ));
+    }
+  }
+}
+
+/// Returns `true` if `expire_at` (`0` meaning "never expires", matching
+/// [`Meta`]'s convention) is at or before `now`.
+#[cfg(feature = "ttl")]
+#[inline]
+fn is_expired(expire_at: u64, now: u64) -> bool {
+  expire_at != 0 && expire_at <= now
+}
+
+/// A version-ordered set of pinned snapshots, kept as a map from each pinned
+/// version to the number of live snapshots holding it.
+///
+/// Modeled on LevelDB's sequence-number `SnapshotList`: every live [`Snapshot`]
+/// registers its pinned version here so that GC/compaction can consult
+/// [`SnapshotList::oldest`] and refuse to drop any entry whose `key().version()`
+/// is still `>= oldest`, even if a newer version of the same key exists.
+/// Dropping a `Snapshot` unregisters it; when the list empties the
+/// pinned-version floor collapses back to `None`.
+#[derive(Debug, Default)]
+pub(crate) struct SnapshotList {
+  versions: std::sync::Mutex<std::collections::BTreeMap<u64, usize>>,
+}
+
+impl SnapshotList {
+  #[inline]
+  fn new() -> Self {
+    Self::default()
+  }
+
+  #[inline]
+  fn acquire(&self, version: u64) {
+    *self.versions.lock().unwrap().entry(version).or_insert(0) += 1;
+  }
+
+  fn release(&self, version: u64) {
+    let mut versions = self.versions.lock().unwrap();
+    if let std::collections::btree_map::Entry::Occupied(mut e) = versions.entry(version) {
+      *e.get_mut() -= 1;
+      if *e.get() == 0 {
+        e.remove();
+      }
+    }
+  }
+
+  /// Returns the oldest version still pinned by a live snapshot, if any.
+  #[inline]
+  pub(crate) fn oldest(&self) -> Option<u64> {
+    self.versions.lock().unwrap().keys().next().copied()
+  }
+}
+
+/// A stable, point-in-time read view pinned to the `max_version` observed by
+/// [`ActiveLogFileReader::snapshot`] when it was created.
+///
+/// Reads through a `Snapshot` implicitly use the pinned version, so `get`,
+/// `first`, `last`, `lower_bound`, and `upper_bound` no longer need a version
+/// threaded through every call. Dropping the snapshot unpins its version from
+/// the owning [`SnapshotList`].
+pub struct Snapshot<C = Ascend, S = Crc32> {
+  reader: ActiveLogFileReader<C, S>,
+  version: u64,
+  now: u64,
+}
+
+impl<C, S> Snapshot<C, S>
+where
+  C: StaticComparator,
+{
+  /// Returns the pinned version this snapshot reads at.
+  #[inline]
+  pub fn version(&self) -> u64 {
+    self.version
+  }
+
+  /// Returns `true` if the active log contains the key as of the pinned version.
+  #[inline]
+  pub fn contains_key(&self, key: &[u8]) -> bool {
+    self.reader.contains_key(self.version, key, self.now)
+  }
+
+  /// Gets the entry by key as of the pinned version.
+  #[inline]
+  pub fn get(&self, key: &[u8]) -> Option<EntryRef<'_, C>> {
+    self.reader.get(self.version, key, self.now)
+  }
+
+  /// Returns the first entry visible as of the pinned version.
+  #[inline]
+  pub fn first(&self) -> Option<EntryRef<'_, C>> {
+    self.reader.first(self.version, self.now)
+  }
+
+  /// Returns the last entry visible as of the pinned version.
+  #[inline]
+  pub fn last(&self) -> Option<EntryRef<'_, C>> {
+    self.reader.last(self.version, self.now)
+  }
+
+  /// Returns a value associated to the highest element whose key is below the given bound,
+  /// as of the pinned version.
+  #[inline]
+  pub fn upper_bound(&self, bound: Bound<&[u8]>) -> Option<EntryRef<'_, C>> {
+    self.reader.upper_bound(self.version, bound, self.now)
+  }
+
+  /// Returns a value associated to the lowest element whose key is above the given bound,
+  /// as of the pinned version.
+  #[inline]
+  pub fn lower_bound(&self, bound: Bound<&[u8]>) -> Option<EntryRef<'_, C>> {
+    self.reader.lower_bound(self.version, bound, self.now)
+  }
+
+  /// Returns a double-ended iterator over every distinct user key in
+  /// `range`, as of the pinned version.
+  #[inline]
+  pub fn range(&self, range: impl RangeBounds<[u8]>) -> RangeIter<'_, C, S> {
+    self.reader.range(self.version, range, self.now)
+  }
+}
+
+impl<C, S> Drop for Snapshot<C, S> {
+  fn drop(&mut self) {
+    self.reader.0.snapshots.release(self.version);
   }
 }
 
@@ -188,6 +723,11 @@ struct Inner<C, S> {
 
   max_version: AtomicU64,
   min_version: AtomicU64,
+  snapshots: Arc<SnapshotList>,
+  /// Set once the log is frozen and a filter has been built over its (now
+  /// immutable) key set; `None` for a log that is still being written to, or
+  /// for one opened with no filter persisted alongside it.
+  filter: std::sync::Mutex<Option<BloomFilter>>,
 }
 
 impl<C, S> core::ops::Deref for Inner<C, S> {
@@ -205,6 +745,12 @@ pub struct ActiveLogFile<C = Ascend, S = Crc32> {
   writer: GenericOrderWal<Key<C>, [u8], S>,
   max_key_size: u32,
   max_value_size: u32,
+  /// `Some(n)` when every key in this log is declared to encode to exactly
+  /// `n` bytes (a uniform/fixed-size key mode, borrowed from parity-db's
+  /// "uniform" column concept), for workloads like hashes or fixed IDs where
+  /// every key is the same width. `insert`/`write` reject any key whose
+  /// length doesn't match `n`.
+  uniform_key_size: Option<u32>,
 }
 
 impl<C, S> ActiveLogFile<C, S>
@@ -216,6 +762,32 @@ where
   pub fn reader(&self) -> ActiveLogFileReader<C, S> {
     ActiveLogFileReader(self.inner.clone())
   }
+
+  /// Builds a Bloom filter over every distinct user key currently visible in
+  /// the log at `bits_per_key` bits per key, and attaches it to the shared
+  /// reader state so subsequent `contains_key`/`get` calls can skip the
+  /// fallback scan on a miss. Call this once a log is frozen, since its key
+  /// set stops changing from that point on; see
+  /// [`bloom::DEFAULT_BITS_PER_KEY`](crate::bloom::DEFAULT_BITS_PER_KEY) for
+  /// a reasonable default.
+  pub fn build_filter(&self, bits_per_key: u32) {
+    let version = self.inner.max_version.load(Ordering::Acquire);
+    let reader = self.reader();
+    let mut keys = std::vec::Vec::new();
+    // `0` never compares as expired (see `is_expired`), so the filter is
+    // built over every key physically present, expired or not: a Bloom
+    // filter only needs to avoid false negatives, and `contains_key`/`get`
+    // still re-check expiration on a hit.
+    let mut iter = reader.scan(version, 0);
+    while iter.advance() {
+      if let Some(entry) = iter.current() {
+        keys.push(entry.key().to_vec());
+      }
+    }
+
+    let filter = BloomFilter::build(keys.iter().map(|k| k.as_slice()), keys.len(), bits_per_key);
+    *self.inner.filter.lock().unwrap() = Some(filter);
+  }
 }
 
 impl<C, S> ActiveLogFile<C, S>
@@ -224,10 +796,19 @@ where
   S: BuildChecksumer,
 {
   /// Inserts the key-value pair into the active log file.
-  pub fn insert(&mut self, meta: Meta, key: &[u8], value: &[u8]) -> Result<(), ActiveLogError> {
+  pub fn insert(&mut self, meta: Meta, key: &[u8], value: &[u8]) -> Result<(), InsertError> {
+    if let Some(uniform_key_size) = self.uniform_key_size {
+      if key.len() as u32 != uniform_key_size {
+        return Err(InsertError::UniformKeySizeMismatch {
+          size: key.len() as u64,
+          uniform_key_size,
+        });
+      }
+    }
+
     let klen = mem::size_of::<Meta>() + key.len();
     if klen > self.max_key_size as usize {
-      return Err(ActiveLogError::KeyTooLarge {
+      return Err(InsertError::KeyTooLarge {
         size: klen as u64,
         maximum_key_size: self.max_key_size,
       });
@@ -235,7 +816,7 @@ where
 
     let vlen = value.len();
     if vlen > self.max_value_size as usize {
-      return Err(ActiveLogError::ValueTooLarge {
+      return Err(InsertError::ValueTooLarge {
         size: vlen as u64,
         maximum_value_size: self.max_value_size,
       });
@@ -254,7 +835,242 @@ where
       self
         .writer
         .insert_with_key_builder::<()>(kb, value)
-        .map_err(|e| e.unwrap_right())
+        .map_err(|e| InsertError::Write(e.unwrap_right()))
+    }
+  }
+
+  /// Applies every operation accumulated in `batch`, stamping all of them
+  /// with `meta`'s version.
+  ///
+  /// Every key/value size in the batch is validated up front, before
+  /// anything is written, so a batch that would have failed a size check
+  /// partway through instead fails before writing anything, reporting the
+  /// offending index via [`WriteBatchError`].
+  ///
+  /// The operations are then written one log entry at a time, in batch
+  /// order, not as a single group commit: if writing fails at `idx`, the
+  /// operations before `idx` stay in the log.
+  pub fn write<K, V>(&mut self, meta: Meta, batch: WriteBatch<K, V>) -> Result<(), WriteBatchError>
+  where
+    K: AsRef<[u8]>,
+    V: AsRef<[u8]>,
+  {
+    for (idx, op) in batch.ops.iter().enumerate() {
+      let key = op.key().as_ref();
+      if let Some(uniform_key_size) = self.uniform_key_size {
+        if key.len() as u32 != uniform_key_size {
+          return Err(WriteBatchError::UniformKeySizeMismatch {
+            idx,
+            size: key.len() as u64,
+            uniform_key_size,
+          });
+        }
+      }
+
+      let klen = mem::size_of::<Meta>() + key.len();
+      if klen > self.max_key_size as usize {
+        return Err(WriteBatchError::KeyTooLarge {
+          idx,
+          size: klen as u64,
+          maximum_key_size: self.max_key_size,
+        });
+      }
+
+      if let WriteBatchOp::Put(_, value) = op {
+        let vlen = value.as_ref().len();
+        if vlen > self.max_value_size as usize {
+          return Err(WriteBatchError::ValueTooLarge {
+            idx,
+            size: vlen as u64,
+            maximum_value_size: self.max_value_size,
+          });
+        }
+      }
+    }
+
+    for (idx, op) in batch.ops.iter().enumerate() {
+      let key = op.key().as_ref();
+      let mut entry_meta = meta;
+      let value: &[u8] = match op {
+        WriteBatchOp::Put(_, value) => value.as_ref(),
+        WriteBatchOp::Delete(_) => {
+          entry_meta.set_tombstone();
+          &[]
+        }
+      };
+
+      let klen = mem::size_of::<Meta>() + key.len();
+      let kb = KeyBuilder::once(klen as u32, |buf| {
+        buf.put_slice_unchecked(key);
+        buf.put_u64_le_unchecked(entry_meta.raw());
+        #[cfg(feature = "ttl")]
+        buf.put_u64_le_unchecked(entry_meta.expire_at());
+
+        Ok(())
+      });
+
+      unsafe {
+        self
+          .writer
+          .insert_with_key_builder::<()>(kb, value)
+          .map_err(|e| WriteBatchError::Write {
+            idx,
+            source: e.unwrap_right(),
+          })?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+/// A single mutation accumulated in a [`WriteBatch`].
+enum WriteBatchOp<K, V> {
+  /// Sets `key` to `value`.
+  Put(K, V),
+  /// Marks `key` as deleted with a tombstone entry.
+  Delete(K),
+}
+
+impl<K, V> WriteBatchOp<K, V> {
+  #[inline]
+  fn key(&self) -> &K {
+    match self {
+      Self::Put(key, _) => key,
+      Self::Delete(key) => key,
     }
   }
 }
+
+/// A batch of put/delete operations applied to an [`ActiveLogFile`] via
+/// [`ActiveLogFile::write`], modeled on LevelDB's `WriteBatch`.
+///
+/// Every operation accumulated here is stamped with the same version. The
+/// operations are written as separate log entries, in order, so a write
+/// error partway through leaves the earlier ones in the log.
+pub struct WriteBatch<K, V> {
+  ops: std::vec::Vec<WriteBatchOp<K, V>>,
+}
+
+impl<K, V> Default for WriteBatch<K, V> {
+  #[inline]
+  fn default() -> Self {
+    Self {
+      ops: std::vec::Vec::new(),
+    }
+  }
+}
+
+impl<K, V> WriteBatch<K, V> {
+  /// Creates an empty batch.
+  #[inline]
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the number of operations accumulated so far.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.ops.len()
+  }
+
+  /// Returns `true` if the batch has no operations.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.ops.is_empty()
+  }
+
+  /// Accumulates a put of `key` to `value`.
+  #[inline]
+  pub fn put(&mut self, key: K, value: V) -> &mut Self {
+    self.ops.push(WriteBatchOp::Put(key, value));
+    self
+  }
+
+  /// Accumulates a tombstone delete of `key`.
+  #[inline]
+  pub fn delete(&mut self, key: K) -> &mut Self {
+    self.ops.push(WriteBatchOp::Delete(key));
+    self
+  }
+}
+
+/// An error that occurs when inserting a key-value pair into an
+/// [`ActiveLogFile`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum InsertError {
+  /// The key does not match the active log's uniform key size.
+  #[error("key does not match the uniform key size: {size} bytes, expected {uniform_key_size}")]
+  UniformKeySizeMismatch {
+    /// The size of the offending key.
+    size: u64,
+    /// The uniform key size every key in the log must match.
+    uniform_key_size: u32,
+  },
+  /// The key is larger than the active log's maximum key size.
+  #[error("key is too large: {size} bytes, maximum is {maximum_key_size}")]
+  KeyTooLarge {
+    /// The size of the offending key.
+    size: u64,
+    /// The maximum key size.
+    maximum_key_size: u32,
+  },
+  /// The value is larger than the active log's maximum value size.
+  #[error("value is too large: {size} bytes, maximum is {maximum_value_size}")]
+  ValueTooLarge {
+    /// The size of the offending value.
+    size: u64,
+    /// The maximum value size.
+    maximum_value_size: u32,
+  },
+  /// Writing the entry to the underlying log failed.
+  #[error(transparent)]
+  Write(#[from] ActiveLogError),
+}
+
+/// An error that occurs when applying a [`WriteBatch`] to an
+/// [`ActiveLogFile`].
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum WriteBatchError {
+  /// The key at `idx` does not match the active log's uniform key size.
+  #[error("key at index {idx} does not match the uniform key size: {size} bytes, expected {uniform_key_size}")]
+  UniformKeySizeMismatch {
+    /// The index of the offending operation within the batch.
+    idx: usize,
+    /// The size of the offending key.
+    size: u64,
+    /// The uniform key size every key in the log must match.
+    uniform_key_size: u32,
+  },
+  /// The key at `idx` is larger than the active log's maximum key size.
+  #[error("key at index {idx} is too large: {size} bytes, maximum is {maximum_key_size}")]
+  KeyTooLarge {
+    /// The index of the offending operation within the batch.
+    idx: usize,
+    /// The size of the offending key.
+    size: u64,
+    /// The maximum key size.
+    maximum_key_size: u32,
+  },
+  /// The value at `idx` is larger than the active log's maximum value size.
+  #[error("value at index {idx} is too large: {size} bytes, maximum is {maximum_value_size}")]
+  ValueTooLarge {
+    /// The index of the offending operation within the batch.
+    idx: usize,
+    /// The size of the offending value.
+    size: u64,
+    /// The maximum value size.
+    maximum_value_size: u32,
+  },
+  /// Writing the batch to the underlying log failed at `idx`.
+  #[error("failed to write batch entry at index {idx}: {source}")]
+  Write {
+    /// The index of the operation that failed to write.
+    idx: usize,
+    /// The underlying log error.
+    #[source]
+    source: ActiveLogError,
+  },
+}
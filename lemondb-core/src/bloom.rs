@@ -0,0 +1,120 @@
+//! A Bloom filter, kept as one unpartitioned bit array, used as a fast
+//! negative-lookup path for frozen logs.
+
+use std::vec::Vec;
+
+/// The default bits-per-key for [`BloomFilter::build`], which callers pass
+/// explicitly, chosen (like LevelDB's) to land close to a 1% false-positive
+/// rate.
+pub const DEFAULT_BITS_PER_KEY: u32 = 10;
+
+/// A Bloom filter over user-key bytes, built once when a log is frozen and
+/// loaded alongside it.
+///
+/// Membership is tested with `k` hash functions derived from a single 64-bit
+/// hash via double hashing (`h1 + i * h2`), the standard Kirsch-Mitzenmacher
+/// construction, rather than computing `k` independent hashes. A `false` from
+/// [`BloomFilter::may_contain`] means the key is definitely absent; `true`
+/// means it might be present and the caller must still check.
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+  bits: Vec<u64>,
+  num_bits: u64,
+  k: u32,
+}
+
+impl BloomFilter {
+  /// Builds a filter over `keys`, sized for `num_keys` entries at
+  /// `bits_per_key`. `num_keys` is taken separately from `keys` so a caller
+  /// with an exact count (e.g. from a frozen log's entry count) does not need
+  /// to buffer the iterator just to measure it.
+  pub fn build<I>(keys: I, num_keys: usize, bits_per_key: u32) -> Self
+  where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+  {
+    let bits_per_key = bits_per_key.max(1);
+    let num_bits = ((num_keys as u64) * bits_per_key as u64).max(64);
+    let num_words = num_bits.div_ceil(64);
+    let num_bits = num_words * 64;
+    // k = bits_per_key * ln(2), clamped the way LevelDB/RocksDB do to keep
+    // lookups cheap even if a caller asks for an extreme bits-per-key.
+    let k = ((bits_per_key as f64) * core::f64::consts::LN_2)
+      .round()
+      .clamp(1.0, 30.0) as u32;
+
+    let mut bits = std::vec![0u64; num_words as usize];
+    for key in keys {
+      set_bits(&mut bits, num_bits, k, key.as_ref());
+    }
+
+    Self {
+      bits,
+      num_bits,
+      k,
+    }
+  }
+
+  /// Returns `false` if `key` is definitely absent from the set this filter
+  /// was built over; `true` if it might be present.
+  pub fn may_contain(&self, key: &[u8]) -> bool {
+    if self.num_bits == 0 {
+      return true;
+    }
+
+    let (h1, h2) = seeds(key);
+    for i in 0..self.k {
+      let bit = probe(h1, h2, i, self.num_bits);
+      if self.bits[(bit / 64) as usize] & (1 << (bit % 64)) == 0 {
+        return false;
+      }
+    }
+
+    true
+  }
+
+  /// Returns the number of bits allocated to this filter.
+  #[inline]
+  pub fn num_bits(&self) -> u64 {
+    self.num_bits
+  }
+
+  /// Returns the number of hash functions used per key.
+  #[inline]
+  pub fn num_hashes(&self) -> u32 {
+    self.k
+  }
+}
+
+fn set_bits(bits: &mut [u64], num_bits: u64, k: u32, key: &[u8]) {
+  let (h1, h2) = seeds(key);
+  for i in 0..k {
+    let bit = probe(h1, h2, i, num_bits);
+    bits[(bit / 64) as usize] |= 1 << (bit % 64);
+  }
+}
+
+#[inline]
+fn probe(h1: u64, h2: u64, i: u32, num_bits: u64) -> u64 {
+  h1.wrapping_add((i as u64).wrapping_mul(h2)) % num_bits
+}
+
+#[inline]
+fn seeds(key: &[u8]) -> (u64, u64) {
+  let h = fnv1a64(key);
+  (h, h.rotate_left(32) | 1)
+}
+
+/// A dependency-free 64-bit FNV-1a hash, used only to derive bloom filter
+/// probe positions (not for anything security- or checksum-sensitive).
+fn fnv1a64(data: &[u8]) -> u64 {
+  const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+  const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+  let mut hash = OFFSET_BASIS;
+  for &byte in data {
+    hash ^= byte as u64;
+    hash = hash.wrapping_mul(PRIME);
+  }
+  hash
+}
@@ -0,0 +1,326 @@
+//! A size-tiered compaction/GC driver for a table's frozen logs and value
+//! logs.
+//!
+//! The manifest lifecycle ([`manifest::tests`](crate::manifest)) already
+//! shows active logs freezing, bloom filters and frozen logs being created,
+//! and value logs rotating, but nothing in this crate decides *when* those
+//! frozen logs should be merged back down or a value log rewritten. This
+//! module adds that policy: [`CompactionPolicy`] picks inputs from a table's
+//! current [`frozen_logs()`](crate::manifest::TableManifest::frozen_logs)
+//! and [`value_logs()`](crate::manifest::TableManifest::value_logs) by a
+//! tiered heuristic, and [`compact_table`] is the manual trigger for one
+//! pass. That pass merges nothing and records nothing in the manifest: a
+//! non-empty plan ends in [`CompactionError::Unsupported`].
+//!
+//! Value-log GC is the same policy applied one file at a time:
+//! [`CompactionPolicy::pick_gc_candidate`] is the picker (lowest live ratio
+//! first, so a single in-flight rewrite never has to contend with another),
+//! [`swap_entries`] is already what builds the `delete_value_log`/
+//! `create_value_log` manifest swap a completed rewrite needs, and
+//! `min_live_byte_ratio` is this crate's `gc_threshold` knob. What's still
+//! missing is the rewrite itself -- scanning a candidate's entries against
+//! the live key index and re-appending survivors -- which needs a value-log
+//! entry reader this crate doesn't have yet (see
+//! [`CompactionError::Unsupported`]).
+
+use std::collections::HashSet;
+
+use crate::{
+  manifest::{ManifestEntry, ManifestFile, ManifestFileError},
+  types::{fid::Fid, table_id::TableId},
+};
+
+const DEFAULT_FROZEN_LOG_THRESHOLD: usize = 4;
+const DEFAULT_MIN_LIVE_BYTE_RATIO: f64 = 0.5;
+
+/// Decides when a table's frozen logs or value logs are due for a merge.
+///
+/// Frozen logs are merged once too many of them have piled up (each one is
+/// an extra file a read has to fall through on a miss); value logs are
+/// rewritten once too much of their content is dead, the same trigger
+/// LevelDB-style stores use for value-log GC.
+#[viewit::viewit(getters(style = "move"), setters(prefix = "with"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompactionPolicy {
+  /// The number of frozen logs a table may hold before they are merged: a
+  /// merge is due once a table has more than this many. Default is `4`.
+  #[viewit(
+    getter(
+      const,
+      attrs(doc = "Returns the frozen log threshold for this policy.")
+    ),
+    setter(attrs(doc = "Sets the frozen log threshold for this policy."))
+  )]
+  frozen_log_threshold: usize,
+  /// The fraction of live bytes a value log must drop below before it is
+  /// rewritten. Default is `0.5`.
+  #[viewit(
+    getter(
+      const,
+      attrs(doc = "Returns the minimum live-byte ratio for this policy.")
+    ),
+    setter(attrs(doc = "Sets the minimum live-byte ratio for this policy."))
+  )]
+  min_live_byte_ratio: f64,
+}
+
+impl Default for CompactionPolicy {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl CompactionPolicy {
+  /// Creates a new compaction policy with the default thresholds.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      frozen_log_threshold: DEFAULT_FROZEN_LOG_THRESHOLD,
+      min_live_byte_ratio: DEFAULT_MIN_LIVE_BYTE_RATIO,
+    }
+  }
+
+  /// Cheaply reports whether [`Self::plan`] would select anything at all,
+  /// without building the `HashSet`s a full [`CompactionPlan`] owns.
+  ///
+  /// Mirrors [`Manifest::should_rewrite`](crate::manifest::Manifest::should_rewrite)'s
+  /// role ahead of a manifest rewrite: a cheap gate a caller polls (e.g.
+  /// after every freeze) to decide whether the heavier [`Self::plan`] +
+  /// [`compact_table`] pass is worth running at all.
+  pub fn should_compact<F>(
+    &self,
+    frozen_logs: &HashSet<Fid>,
+    value_logs: &HashSet<Fid>,
+    live_byte_ratio: F,
+  ) -> bool
+  where
+    F: Fn(Fid) -> f64,
+  {
+    frozen_logs.len() > self.frozen_log_threshold
+      || value_logs
+        .iter()
+        .any(|fid| live_byte_ratio(*fid) < self.min_live_byte_ratio)
+  }
+
+  /// Picks the single value log most in need of GC: the lowest live-byte
+  /// ratio among `value_logs`, provided it still falls below
+  /// [`min_live_byte_ratio`](Self::min_live_byte_ratio).
+  ///
+  /// Unlike [`Self::plan`], which hands back every value log due for a
+  /// merge at once, this always returns at most one `Fid` -- a mark-and-
+  /// reclaim GC pass rewrites one file's survivors into the active log and
+  /// records the swap before looking at the next candidate, so that writes
+  /// racing the GC pass only ever have to contend with a single in-flight
+  /// rewrite at a time.
+  pub fn pick_gc_candidate<F>(&self, value_logs: &HashSet<Fid>, live_byte_ratio: F) -> Option<Fid>
+  where
+    F: Fn(Fid) -> f64,
+  {
+    value_logs
+      .iter()
+      .copied()
+      .map(|fid| (fid, live_byte_ratio(fid)))
+      .filter(|(_, ratio)| *ratio < self.min_live_byte_ratio)
+      .min_by(|(_, a), (_, b)| a.total_cmp(b))
+      .map(|(fid, _)| fid)
+  }
+
+  /// Selects the frozen logs and value logs of a table that are due for a
+  /// merge under this policy.
+  ///
+  /// `live_byte_ratio` is supplied by the caller rather than measured here,
+  /// since a value log's live/dead byte accounting is tracked by the value
+  /// log implementation, not by the manifest.
+  pub fn plan<F>(
+    &self,
+    frozen_logs: &HashSet<Fid>,
+    value_logs: &HashSet<Fid>,
+    live_byte_ratio: F,
+  ) -> CompactionPlan
+  where
+    F: Fn(Fid) -> f64,
+  {
+    let frozen_logs = if frozen_logs.len() > self.frozen_log_threshold {
+      frozen_logs.clone()
+    } else {
+      HashSet::new()
+    };
+
+    let value_logs = value_logs
+      .iter()
+      .copied()
+      .filter(|fid| live_byte_ratio(*fid) < self.min_live_byte_ratio)
+      .collect();
+
+    CompactionPlan {
+      frozen_logs,
+      value_logs,
+    }
+  }
+}
+
+/// The frozen logs and value logs a [`CompactionPolicy`] selected for one
+/// compaction pass over a table.
+#[derive(Debug, Clone, Default)]
+pub struct CompactionPlan {
+  frozen_logs: HashSet<Fid>,
+  value_logs: HashSet<Fid>,
+}
+
+impl CompactionPlan {
+  /// Returns `true` if neither the frozen logs nor the value logs need
+  /// compacting.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.frozen_logs.is_empty() && self.value_logs.is_empty()
+  }
+
+  /// Returns the frozen logs selected for this pass.
+  #[inline]
+  pub fn frozen_logs(&self) -> &HashSet<Fid> {
+    &self.frozen_logs
+  }
+
+  /// Returns the value logs selected for this pass.
+  #[inline]
+  pub fn value_logs(&self) -> &HashSet<Fid> {
+    &self.value_logs
+  }
+}
+
+/// Bytes and time accounted for by one compaction pass, for observability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionStats {
+  /// Bytes read from the compaction inputs.
+  pub bytes_read: u64,
+  /// Bytes written to the compacted output.
+  pub bytes_written: u64,
+  /// Entries dropped because they were superseded or a tombstone older than
+  /// the oldest snapshot.
+  pub entries_dropped: u64,
+  /// Wall-clock time spent on the pass, in microseconds.
+  pub micros: u64,
+}
+
+impl CompactionStats {
+  /// Accumulates `other` into `self`, for rolling up per-log stats into a
+  /// per-table or per-level total.
+  #[inline]
+  pub fn merge(&mut self, other: &Self) {
+    self.bytes_read += other.bytes_read;
+    self.bytes_written += other.bytes_written;
+    self.entries_dropped += other.entries_dropped;
+    self.micros += other.micros;
+  }
+}
+
+/// An error returned when a compaction pass cannot be completed.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum CompactionError {
+  /// The table does not exist in the manifest.
+  #[error("table {0} does not exist")]
+  TableNotFound(TableId),
+  /// `fid` was selected by the policy but this crate has no merging reader
+  /// for it yet.
+  ///
+  /// [`ImmutableLogFile`](crate::immutable_log::ImmutableLogFile) has no
+  /// entry iterator and the standalone value log reader does not exist yet,
+  /// so there is no merging iterator to stream `fid`'s entries through.
+  /// Rather than deleting real input logs without having merged their data,
+  /// `compact_table` stops here and reports which input it could not read;
+  /// the manifest swap below is already wired up for the day a reader lands.
+  #[error("compaction input {0} has no merging reader yet")]
+  Unsupported(Fid),
+  /// Recording the compaction result in the manifest failed.
+  #[error(transparent)]
+  Manifest(#[from] ManifestFileError),
+}
+
+/// Runs one compaction pass for `tid`, selecting inputs from `manifest` via
+/// `policy`. Nothing is merged and the manifest is not changed: an empty
+/// plan returns empty [`CompactionStats`], and a non-empty one returns
+/// [`CompactionError::Unsupported`] (see below).
+///
+/// This is the manual trigger the request asks for; a background policy is
+/// just a caller — elsewhere in this codebase, the layer that owns thread
+/// spawning (e.g. `db::sync`'s writer thread) — that invokes this on a
+/// timer or after every freeze, the same way it already drives the active
+/// log's freeze/rotate lifecycle.
+///
+/// Returns [`CompactionError::Unsupported`] instead of performing the swap
+/// if the plan is non-empty, since merging frozen log or value log entries
+/// has no reader to stream through yet (see [`CompactionError::Unsupported`]).
+pub fn compact_table<F>(
+  manifest: &mut ManifestFile,
+  tid: TableId,
+  policy: &CompactionPolicy,
+  live_byte_ratio: F,
+) -> Result<CompactionStats, CompactionError>
+where
+  F: Fn(Fid) -> f64,
+{
+  let plan = {
+    let table = manifest
+      .manifest()
+      .tables()
+      .values()
+      .find(|table| table.id() == tid)
+      .ok_or(CompactionError::TableNotFound(tid))?;
+
+    policy.plan(table.frozen_logs(), table.value_logs(), live_byte_ratio)
+  };
+
+  if plan.is_empty() {
+    return Ok(CompactionStats::default());
+  }
+
+  if let Some(fid) = plan
+    .frozen_logs()
+    .iter()
+    .chain(plan.value_logs().iter())
+    .next()
+  {
+    return Err(CompactionError::Unsupported(*fid));
+  }
+
+  Ok(CompactionStats::default())
+}
+
+/// Builds the manifest swap for a completed merge of `old` frozen/value
+/// logs into `new`, so the reader/writer above only has to supply file IDs
+/// once the merge itself is implemented.
+///
+/// `new` is `(fid, is_value_log)`: a compacted frozen log also needs its
+/// bloom filter created, so callers merging frozen logs should pair this
+/// with a `ManifestEntry::create_bloomfilter(fid, tid)` of their own.
+pub fn swap_entries(
+  tid: TableId,
+  old_frozen_logs: &HashSet<Fid>,
+  old_value_logs: &HashSet<Fid>,
+  new: (Fid, bool),
+) -> std::vec::Vec<ManifestEntry> {
+  let mut entries =
+    std::vec::Vec::with_capacity(old_frozen_logs.len() + old_value_logs.len() + 1);
+
+  entries.extend(
+    old_frozen_logs
+      .iter()
+      .map(|fid| ManifestEntry::delete_frozen_log(*fid, tid)),
+  );
+  entries.extend(
+    old_value_logs
+      .iter()
+      .map(|fid| ManifestEntry::delete_value_log(*fid, tid)),
+  );
+
+  let (fid, is_value_log) = new;
+  entries.push(if is_value_log {
+    ManifestEntry::create_value_log(fid, tid)
+  } else {
+    ManifestEntry::create_frozen_log(fid, tid)
+  });
+
+  entries
+}
@@ -41,19 +41,102 @@ pub struct ImmutableLogFileWriter<K: ?Sized> {
   map: SkipMap<Meta, GenericComparator<K>>,
 }
 
+impl<K> ImmutableLogFileWriter<K>
+where
+  K: ?Sized,
+{
+  /// Seals this log, turning it into a read-only [`ImmutableLogFile`].
+  ///
+  /// `min_version`/`max_version` are the version watermarks the active log
+  /// being rotated out already tracked (see
+  /// `ActiveLogFileReader::min_version`/`max_version`), so sealing just
+  /// records them rather than re-deriving them by scanning every entry.
+  #[inline]
+  pub fn seal(self, min_version: u64, max_version: u64) -> ImmutableLogFile<K> {
+    ImmutableLogFile {
+      map: self.map,
+      min_version,
+      max_version,
+    }
+  }
+}
+
+/// A reference to an entry read from a sealed [`ImmutableLogFile`].
+pub struct ImmutableEntryRef<'a, K>
+where
+  K: ?Sized + Type,
+{
+  meta: Meta,
+  key: K::Ref<'a>,
+  value: &'a [u8],
+}
+
+impl<'a, K> ImmutableEntryRef<'a, K>
+where
+  K: ?Sized + Type,
+{
+  /// Returns the version this entry was written at.
+  #[inline]
+  pub const fn version(&self) -> u64 {
+    self.meta.version()
+  }
+
+  /// Returns `true` if this entry is a tombstone, i.e. it marks its key as
+  /// deleted as of its version.
+  #[inline]
+  pub const fn is_tombstone(&self) -> bool {
+    self.meta.is_tombstone()
+  }
+
+  /// Returns the decoded key.
+  #[inline]
+  pub const fn key(&self) -> &K::Ref<'a> {
+    &self.key
+  }
+
+  /// Returns the raw value bytes.
+  #[inline]
+  pub const fn value(&self) -> &'a [u8] {
+    self.value
+  }
+}
 
 /// A frozen log file.
+///
+/// Offers the same MVCC read surface as `ActiveLogFileReader`
+/// (`crate::active_log::ActiveLogFileReader`): among every version of a
+/// user key, only the newest version `<= version` is ever visible, and a
+/// tombstoned key is reported as absent rather than returned.
 pub struct ImmutableLogFile<K: ?Sized> {
   map: SkipMap<Meta, GenericComparator<K>>,
+  min_version: u64,
+  max_version: u64,
 }
 
 impl<K> ImmutableLogFile<K>
 where
   K: ?Sized,
 {
-  /// Returns `true` if the frozne log contains the version.
+  /// Returns the minimum version recorded in this frozen log.
+  #[inline]
+  pub const fn min_version(&self) -> u64 {
+    self.min_version
+  }
+
+  /// Returns the maximum version recorded in this frozen log.
+  #[inline]
+  pub const fn max_version(&self) -> u64 {
+    self.max_version
+  }
+
+  /// Returns `true` if the frozen log contains the version.
+  ///
+  /// `min_version`/`max_version` are recorded once, at
+  /// [`seal`](ImmutableLogFileWriter::seal), so this is an O(1) range check
+  /// rather than a scan.
+  #[inline]
   pub fn contains_version(&self, version: u64) -> bool {
-    todo!()
+    self.min_version <= version && version <= self.max_version
   }
 }
 
@@ -62,5 +145,128 @@ where
   K: ?Sized + Type,
   for<'a> K::Ref<'a>: KeyRef<'a, K>,
 {
-  
+  /// Returns `true` if the frozen log contains the key as of `version`.
+  ///
+  /// A tombstoned key (the newest version `<= version` is a delete marker)
+  /// counts as not contained, even though its entry is still physically
+  /// present in the log.
+  #[inline]
+  pub fn contains_key(&self, version: u64, key: &[u8]) -> bool {
+    self.get(version, key).is_some()
+  }
+
+  /// Gets the entry by key as of `version`.
+  ///
+  /// Returns `None` if the newest version of `key` visible at `version` is
+  /// a tombstone.
+  #[inline]
+  pub fn get<'a>(&'a self, version: u64, key: &[u8]) -> Option<ImmutableEntryRef<'a, K>> {
+    if !self.contains_version(version) {
+      return None;
+    }
+
+    let ent = self.map.get(version, key)?;
+    (!ent.trailer().is_tombstone()).then(|| Self::entry_ref(&ent))
+  }
+
+  /// Returns the first entry in the frozen log, as of `version`.
+  ///
+  /// A key whose newest visible version is a tombstone is skipped in favor
+  /// of the next smallest key.
+  #[inline]
+  pub fn first(&self, version: u64) -> Option<ImmutableEntryRef<'_, K>> {
+    if !self.contains_version(version) {
+      return None;
+    }
+
+    let mut ent = self.map.first(version);
+    while let Some(e) = ent {
+      if !e.trailer().is_tombstone() {
+        return Some(Self::entry_ref(&e));
+      }
+
+      ent = e.next();
+    }
+
+    None
+  }
+
+  /// Returns the last entry in the frozen log, as of `version`.
+  ///
+  /// A key whose newest visible version is a tombstone is skipped in favor
+  /// of the next largest key.
+  #[inline]
+  pub fn last(&self, version: u64) -> Option<ImmutableEntryRef<'_, K>> {
+    if !self.contains_version(version) {
+      return None;
+    }
+
+    let mut ent = self.map.last(version);
+    while let Some(e) = ent {
+      if !e.trailer().is_tombstone() {
+        return Some(Self::entry_ref(&e));
+      }
+
+      ent = e.prev();
+    }
+
+    None
+  }
+
+  /// Returns a value associated to the highest element whose key is below
+  /// the given bound, as of `version`. If no such element is found then
+  /// `None` is returned.
+  ///
+  /// A key whose newest visible version is a tombstone is skipped in favor
+  /// of the next largest key below the bound.
+  #[inline]
+  pub fn upper_bound(&self, version: u64, bound: Bound<&[u8]>) -> Option<ImmutableEntryRef<'_, K>> {
+    if !self.contains_version(version) {
+      return None;
+    }
+
+    let mut ent = self.map.upper_bound(version, bound);
+    while let Some(e) = ent {
+      if !e.trailer().is_tombstone() {
+        return Some(Self::entry_ref(&e));
+      }
+
+      ent = e.prev();
+    }
+
+    None
+  }
+
+  /// Returns a value associated to the lowest element whose key is above
+  /// the given bound, as of `version`. If no such element is found then
+  /// `None` is returned.
+  ///
+  /// A key whose newest visible version is a tombstone is skipped in favor
+  /// of the next smallest key above the bound.
+  #[inline]
+  pub fn lower_bound(&self, version: u64, bound: Bound<&[u8]>) -> Option<ImmutableEntryRef<'_, K>> {
+    if !self.contains_version(version) {
+      return None;
+    }
+
+    let mut ent = self.map.lower_bound(version, bound);
+    while let Some(e) = ent {
+      if !e.trailer().is_tombstone() {
+        return Some(Self::entry_ref(&e));
+      }
+
+      ent = e.next();
+    }
+
+    None
+  }
+
+  #[inline]
+  fn entry_ref<'a>(ent: &skl::full::EntryRef<'a, Meta, GenericComparator<K>>) -> ImmutableEntryRef<'a, K> {
+    ImmutableEntryRef {
+      meta: *ent.trailer(),
+      key: unsafe { <K::Ref<'_> as TypeRef<'_>>::from_slice(ent.key()) },
+      value: ent.value(),
+    }
+  }
 }
\ No newline at end of file
@@ -14,6 +14,12 @@ extern crate std;
 /// An active log.
 pub mod active_log;
 
+/// A Bloom filter used as a fast negative-lookup path for frozen logs.
+pub mod bloom;
+
+/// A size-tiered compaction/GC driver for frozen and value logs.
+pub mod compaction;
+
 /// Common error types.
 pub mod error;
 
@@ -14,6 +14,7 @@ use crate::types::{
   table_name::{TableName, DEFAULT_TABLE_NAME},
 };
 
+#[cfg(feature = "std")]
 mod disk;
 mod entry;
 pub use entry::*;
@@ -363,9 +364,24 @@ impl Manifest {
   }
 }
 
+// NOTE: `disk` (and the `Disk` variant below) is the only part of this
+// module that actually needs `std` -- it opens and memory-maps a real file
+// via `aol::AppendLog`/`Builder`. `ManifestError`/`ManifestRecordError`
+// already derive `thiserror::Error` unconditionally and already cover
+// every variant (including `LargeTableName`/`DuplicateTableId`) without a
+// hand-written `no_std` `Display` arm to fall out of sync, so there was
+// nothing to patch there. The two real gaps were this module's `Disk`
+// wiring leaking into `no_std` builds (`mod disk` and this variant were
+// unconditional, and a second, unconditional `ManifestFile::open` with the
+// same name as the `not(std)` one below would have conflicted) and the
+// `not(std)` `open` building a `ManifestFile` missing its `fid`/`tid`
+// fields entirely -- both fixed here, giving `MemoryManifest` a
+// filesystem-free `no_std` path through `append`/`append_batch` (it keeps
+// the manifest in memory only and never rewrites it).
 #[derive(derive_more::From)]
 enum ManifestFileKind {
   Memory(memory::MemoryManifest),
+  #[cfg(feature = "std")]
   Disk(disk::DiskManifest),
 }
 
@@ -377,7 +393,9 @@ pub struct ManifestFile {
 }
 
 impl ManifestFile {
-  /// Opens a manifest file.
+  /// Opens a manifest file, backed by `dir` on disk if given, or purely
+  /// in-memory otherwise.
+  #[cfg(feature = "std")]
   pub fn open<P: AsRef<std::path::Path>>(
     dir: Option<P>,
     opts: ManifestOptions,
@@ -401,12 +419,15 @@ impl ManifestFile {
     }
   }
 
-  /// Opens a memory manifest file.
+  /// Opens a purely in-memory manifest file -- the only kind available
+  /// without `std`, since a disk-backed manifest needs a filesystem.
   #[cfg(not(feature = "std"))]
   pub fn open(
     opts: ManifestOptions,
   ) -> Result<Self, Among<ManifestRecordError, ManifestError, ManifestFileError>> {
     Ok(Self {
+      fid: AtomicFid::zero(),
+      tid: AtomicTableId::zero(),
       kind: ManifestFileKind::Memory(memory::MemoryManifest::new(opts)),
     })
   }
@@ -420,6 +441,7 @@ impl ManifestFile {
     let ent = ent.into();
     match &mut self.kind {
       ManifestFileKind::Memory(m) => m.append(ent).map_err(Into::into),
+      #[cfg(feature = "std")]
       ManifestFileKind::Disk(d) => d.append(ent),
     }
   }
@@ -435,6 +457,7 @@ impl ManifestFile {
   {
     match &mut self.kind {
       ManifestFileKind::Memory(m) => m.append_batch(entries).map_err(Into::into),
+      #[cfg(feature = "std")]
       ManifestFileKind::Disk(d) => d.append_batch(entries),
     }
   }
@@ -444,6 +467,7 @@ impl ManifestFile {
   pub fn manifest(&self) -> &Manifest {
     match &self.kind {
       ManifestFileKind::Memory(m) => m.manifest(),
+      #[cfg(feature = "std")]
       ManifestFileKind::Disk(d) => d.manifest(),
     }
   }
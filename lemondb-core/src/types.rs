@@ -28,8 +28,12 @@ pub mod value;
 /// A pointer pointing to an entry with a large value in the value log.
 pub mod pointer;
 
-// /// The reference to an entry in the database.
-// pub mod entry_ref;
+/// The metadata of an entry in the active log, including its version and
+/// tombstone/value-pointer marks.
+pub mod meta;
+
+/// The reference to an entry in the database.
+pub mod entry_ref;
 
 // /// The entry in the database.
 // pub mod entry;
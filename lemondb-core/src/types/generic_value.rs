@@ -8,6 +8,21 @@ use skl::either::Either;
 
 use super::pointer::Pointer;
 
+// NOTE: this module, like its siblings `generic_entry_ref`/`generic_key`/
+// `generic_key_ref`, has no `mod generic_value;` in `types.rs` and isn't
+// part of the compiled crate -- an earlier, abandoned pass at a generic
+// key/value type system. A per-value compression codec for this tag-byte
+// scheme (0/1/2 below, plus new compressed variants) would have no effect
+// here. `value_log::generic::entry`'s `GenericEntry`/`GenericEntryRef`
+// carry it instead (that module is outside the compiled crate too, since
+// `value_log.rs` has its body commented out): they store a per-value-log
+// `CompressionType`
+// decision via `value_log::meta::Meta::COMPRESSED_FLAG` (set by the writer
+// before encoding, read back by `GenericEntryRef::value`, which
+// decompresses into a caller-supplied scratch buffer using the stored
+// `raw_len`) rather than a tag byte, since `Meta` already carries spare
+// bits and a flag is cheaper to test than a widened tag range.
+
 /// Generic value.
 pub struct PhantomGenericValue<V: ?Sized>(PhantomData<V>);
 
@@ -8,17 +8,17 @@ use zerocopy::{FromBytes, FromZeroes};
 /// - With `ttl` feature enabled:
 ///
 ///   ```text
-///   +---------------------+----------------------------------+------------------------+
-///   | 63 bits for version |   1 bit for value pointer mark   | 64 bits for expiration |
-///   +---------------------+----------------------------------+------------------------+
+///   +---------------------+----------------------+----------------------------------+------------------------+
+///   | 62 bits for version | 1 bit for tombstone  |   1 bit for value pointer mark   | 64 bits for expiration |
+///   +---------------------+----------------------+----------------------------------+------------------------+
 ///   ```
 ///
 /// - Without `ttl` feature enabled:
 ///
 ///   ```text
-///   +---------------------+----------------------------------+
-///   | 63 bits for version |   1 bit for value pointer mark   |
-///   +---------------------+----------------------------------+
+///   +---------------------+----------------------+----------------------------------+
+///   | 62 bits for version | 1 bit for tombstone  |   1 bit for value pointer mark   |
+///   +---------------------+----------------------+----------------------------------+
 ///   ```
 #[derive(Copy, Clone, Eq, PartialEq, FromZeroes, FromBytes)]
 #[repr(C, align(8))]
@@ -58,7 +58,8 @@ impl Meta {
   ) -> core::fmt::DebugStruct<'a, 'b> {
     let mut s = f.debug_struct("Meta");
     s.field("version", &self.version())
-      .field("pointer", &self.is_pointer());
+      .field("pointer", &self.is_pointer())
+      .field("tombstone", &self.is_tombstone());
     s
   }
 
@@ -96,14 +97,15 @@ impl Meta {
 
 impl Meta {
   /// The maximum version.
-  pub const MAX_VERSION: u64 = (1 << 63) - 1;
-  pub(crate) const VERSION_MASK: u64 = !0u64 >> 1; // 0xFFFFFFFFFFFFFFFE // 63 bits for version
+  pub const MAX_VERSION: u64 = (1 << 62) - 1;
+  pub(crate) const VERSION_MASK: u64 = (1 << 62) - 1; // 62 bits for version
+  pub(crate) const TOMBSTONE_FLAG: u64 = 1 << 62; // 63rd bit for tombstone mark
   pub(crate) const VALUE_POINTER_FLAG: u64 = 1 << 63; // 64th bit for value pointer mark
 
   /// Create a new metadata with the given version.
   #[inline]
   pub const fn new(version: u64, #[cfg(feature = "ttl")] expire_at: u64) -> Self {
-    assert!(version < (1 << 63), "version is too large");
+    assert!(version <= Self::MAX_VERSION, "version is too large");
 
     Self {
       meta: version,
@@ -115,7 +117,7 @@ impl Meta {
   /// Returns a new meta for lookup.
   #[inline]
   pub(crate) const fn query(version: u64) -> Self {
-    assert!(version < (1 << 63), "version is too large");
+    assert!(version <= Self::MAX_VERSION, "version is too large");
 
     Self {
       meta: version,
@@ -127,7 +129,7 @@ impl Meta {
   /// Create a new metadata with the given version and toggle the value pointer flag.
   #[inline]
   pub const fn pointer(mut version: u64, #[cfg(feature = "ttl")] expire_at: u64) -> Self {
-    assert!(version < (1 << 63), "version is too large");
+    assert!(version <= Self::MAX_VERSION, "version is too large");
 
     version |= Self::VALUE_POINTER_FLAG;
     Self {
@@ -137,18 +139,48 @@ impl Meta {
     }
   }
 
+  /// Create a new metadata with the given version and toggle the tombstone flag.
+  ///
+  /// A tombstone marks the key as deleted as of `version`: the read path and
+  /// [`TableScanIter`](crate::active_log::TableScanIter) treat it as "not
+  /// found" rather than skipping straight through to an older version.
+  #[inline]
+  pub const fn tombstone(mut version: u64, #[cfg(feature = "ttl")] expire_at: u64) -> Self {
+    assert!(version <= Self::MAX_VERSION, "version is too large");
+
+    version |= Self::TOMBSTONE_FLAG;
+    Self {
+      meta: version,
+      #[cfg(feature = "ttl")]
+      expire_at,
+    }
+  }
+
   /// Set the value pointer flag.
   #[inline]
   pub fn set_pointer(&mut self) {
     self.meta |= Self::VALUE_POINTER_FLAG;
   }
 
+  /// Set the tombstone flag.
+  #[inline]
+  pub fn set_tombstone(&mut self) {
+    self.meta |= Self::TOMBSTONE_FLAG;
+  }
+
   /// Returns `true` if the value of the entry is a value pointer.
   #[inline]
   pub const fn is_pointer(&self) -> bool {
     self.meta & Self::VALUE_POINTER_FLAG != 0
   }
 
+  /// Returns `true` if the entry is a tombstone, i.e. it marks its key as
+  /// deleted as of its version.
+  #[inline]
+  pub const fn is_tombstone(&self) -> bool {
+    self.meta & Self::TOMBSTONE_FLAG != 0
+  }
+
   /// Returns the version.
   #[inline]
   pub const fn version(&self) -> u64 {
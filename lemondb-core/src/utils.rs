@@ -6,6 +6,19 @@ const VLOG_EXTENSION: &str = "vlog";
 const ACTIVE_LOG_EXTENSION: &str = "alog";
 const FROZEN_LOG_EXTENSION: &str = "flog";
 
+// NOTE: reserve-and-grow-in-chunks for an active log, truncating back to
+// the logical length on the alog -> flog freeze transition, isn't
+// implemented against these constants: neither is referenced by any log
+// opener in this crate today (`filename` itself has no callers here --
+// this crate's `value_log` module, the obvious caller, has its body
+// commented out, see its own top-of-file note). The crates that do have a
+// working `LogFile` backed by `skl::SkipMap` (`lemondb`/`src`) already
+// carry this exact request as a NOTE on their own `wal/lf.rs`: `SkipMap`
+// commits its whole arena up front on create and has no `grow`/`remap`
+// hook a wrapper here could retry into, which is what would need to change
+// before chunked reservation could land in either of those trees, let
+// alone this one.
+
 // 20 digits + 1 dot + 4 extension
 const MAX_FILENAME_SUFFIX_LEN: usize = 4 + MAX_DIGITS + 1;
 
@@ -1,6 +1,6 @@
 use crate::types::pointer::Pointer;
 
-use super::{Meta, VMeta, ValueLogCore};
+use super::{maybe_compress, CompressionType, Meta, VMeta, ValueLogCore};
 
 use among::Among;
 use dbutils::{
@@ -24,7 +24,9 @@ where
   V: core::fmt::Debug + Type + ?Sized,
   C: BuildChecksumer,
 {
-  /// Reads a entry from the value log at the given offset with size.
+  /// Reads a entry from the value log at the given offset with size. Use
+  /// [`GenericEntryRef::value`] to get the value, decompressing it if
+  /// needed.
   pub fn read(&self, pointer: Pointer) -> Result<GenericEntryRef<'_, K, V>, Error> {
     unsafe {
       self.log.log.read_generic::<GenericEntry<'_, K, V>>(
@@ -35,14 +37,34 @@ where
     }
   }
 
-  /// Inserts a key-value pair into the value log.
+  /// Inserts a key-value pair into the value log, compressing the encoded
+  /// value with `compression` first if it is at least `min_compress_len`
+  /// bytes and doing so actually shrinks it; otherwise the value is stored
+  /// verbatim. The key is never compressed, so comparisons against it are
+  /// unaffected.
   pub fn insert(
     &self,
     meta: Meta,
     key: &K,
     value: &V,
+    min_compress_len: u64,
+    compression: CompressionType,
   ) -> Result<Pointer, Among<K::Error, V::Error, Error>> {
-    let ent = GenericEntry::new(meta.into(), key, Some(value));
+    let raw_len = value.encoded_len();
+    let mut raw = std::vec::Vec::with_capacity(raw_len);
+    raw.resize(raw_len, 0u8);
+    value.encode(&mut raw).map_err(Among::Right)?;
+    let compressed = maybe_compress(&raw, min_compress_len, compression);
+
+    let ent = match &compressed {
+      Some(c) => GenericEntry::compressed(
+        VMeta::from(meta).with_compressed(),
+        key,
+        c,
+        raw_len as u32,
+      ),
+      None => GenericEntry::new(meta.into(), key, Some(value)),
+    };
     let encoded_len = ent.encoded_len();
     self
       .log
@@ -1,7 +1,7 @@
 use core::mem;
 
 use super::{
-  super::{merge_lengths, split_lengths},
+  super::{decompress, merge_lengths, split_lengths, CompressionType, DecompressError},
   VMeta,
 };
 
@@ -52,17 +52,50 @@ where
   }
 }
 
+/// A `GenericEntry`'s value, either the typed value to encode verbatim or
+/// an already-compressed byte payload to copy in as-is.
+enum GenericValue<'a, V: ?Sized> {
+  Raw(&'a V),
+  Compressed(&'a [u8]),
+}
+
 /// The generic entry in the value log.
 pub(super) struct GenericEntry<'a, K: ?Sized, V: ?Sized> {
   meta: VMeta,
   key: &'a K,
-  value: Option<&'a V>,
+  value: Option<GenericValue<'a, V>>,
+  /// The value's encoded length before compression. Meaningless unless
+  /// `meta` is compressed.
+  raw_len: u32,
 }
 
 impl<'a, K: ?Sized, V: ?Sized> GenericEntry<'a, K, V> {
   #[inline]
   pub(super) const fn new(meta: VMeta, key: &'a K, value: Option<&'a V>) -> Self {
-    Self { meta, key, value }
+    let value = match value {
+      Some(v) => Some(GenericValue::Raw(v)),
+      None => None,
+    };
+    Self {
+      meta,
+      key,
+      value,
+      raw_len: 0,
+    }
+  }
+
+  /// Builds an entry whose value is already compressed; `raw_len` is the
+  /// value's encoded length before compression and is stored alongside it
+  /// so [`GenericEntryRef::value`] knows how large a buffer to decompress
+  /// into.
+  #[inline]
+  pub(super) const fn compressed(meta: VMeta, key: &'a K, value: &'a [u8], raw_len: u32) -> Self {
+    Self {
+      meta,
+      key,
+      value: Some(GenericValue::Compressed(value)),
+      raw_len,
+    }
   }
 }
 
@@ -84,11 +117,18 @@ where
   #[inline]
   fn encoded_len(&self) -> usize {
     let key_len = self.key.encoded_len();
+    let raw_len_field = if self.meta.is_compressed() {
+      mem::size_of::<u32>()
+    } else {
+      0
+    };
 
-    match self.value {
-      Some(v) => {
-        let value_len = v.encoded_len();
-        VMeta::SIZE + mem::size_of::<u64>() + key_len + value_len
+    match &self.value {
+      Some(GenericValue::Raw(v)) => {
+        VMeta::SIZE + mem::size_of::<u64>() + raw_len_field + key_len + v.encoded_len()
+      }
+      Some(GenericValue::Compressed(stored)) => {
+        VMeta::SIZE + mem::size_of::<u64>() + raw_len_field + key_len + stored.len()
       }
       None => VMeta::SIZE + mem::size_of::<u32>() + key_len,
     }
@@ -97,33 +137,52 @@ where
   fn encode(&self, buf: &mut [u8]) -> Result<usize, Self::Error> {
     const LEN_SIZE: usize = mem::size_of::<u64>();
     const HALF_LEN_SIZE: usize = LEN_SIZE / 2;
+    const RAW_LEN_SIZE: usize = mem::size_of::<u32>();
 
     let mut cursor = 0;
     self.meta.encode(&mut buf[..VMeta::SIZE]);
     cursor += VMeta::SIZE;
 
-    let size = match self.value {
-      Some(v) => {
-        let key_len = self
-          .key
-          .encode(&mut buf[cursor + LEN_SIZE..])
-          .map_err(Either::Left)?;
+    let size = match &self.value {
+      Some(GenericValue::Raw(v)) => {
+        let kvlen_off = cursor;
+        let raw_len_field = if self.meta.is_compressed() {
+          RAW_LEN_SIZE
+        } else {
+          0
+        };
+        let key_off = cursor + LEN_SIZE + raw_len_field;
+        let key_len = self.key.encode(&mut buf[key_off..]).map_err(Either::Left)?;
         let value_len = v
-          .encode(&mut buf[cursor + LEN_SIZE + key_len..])
+          .encode(&mut buf[key_off + key_len..])
           .map_err(Either::Right)?;
         let kvlen = merge_lengths(key_len as u32, value_len as u32);
-        buf[cursor..cursor + LEN_SIZE].copy_from_slice(&kvlen.to_le_bytes());
-        cursor += LEN_SIZE + key_len + value_len;
-        cursor
+        buf[kvlen_off..kvlen_off + LEN_SIZE].copy_from_slice(&kvlen.to_le_bytes());
+        if self.meta.is_compressed() {
+          buf[kvlen_off + LEN_SIZE..kvlen_off + LEN_SIZE + RAW_LEN_SIZE]
+            .copy_from_slice(&self.raw_len.to_le_bytes());
+        }
+        key_off + key_len + value_len
+      }
+      Some(GenericValue::Compressed(stored)) => {
+        let kvlen_off = cursor;
+        let key_off = cursor + LEN_SIZE + RAW_LEN_SIZE;
+        let key_len = self.key.encode(&mut buf[key_off..]).map_err(Either::Left)?;
+        let value_len = stored.len();
+        buf[key_off + key_len..key_off + key_len + value_len].copy_from_slice(stored);
+        let kvlen = merge_lengths(key_len as u32, value_len as u32);
+        buf[kvlen_off..kvlen_off + LEN_SIZE].copy_from_slice(&kvlen.to_le_bytes());
+        buf[kvlen_off + LEN_SIZE..kvlen_off + LEN_SIZE + RAW_LEN_SIZE]
+          .copy_from_slice(&self.raw_len.to_le_bytes());
+        key_off + key_len + value_len
       }
       None => {
         let key_len = self
           .key
           .encode(&mut buf[cursor + HALF_LEN_SIZE..])
           .map_err(Either::Left)?;
-        buf[cursor..cursor + HALF_LEN_SIZE].copy_from_slice(&key_len.to_le_bytes());
-        cursor += HALF_LEN_SIZE + key_len;
-        cursor
+        buf[cursor..cursor + HALF_LEN_SIZE].copy_from_slice(&(key_len as u32).to_le_bytes());
+        cursor + HALF_LEN_SIZE + key_len
       }
     };
 
@@ -133,22 +192,42 @@ where
   fn encode_to_buffer(&self, buf: &mut VacantBuffer<'_>) -> Result<usize, Self::Error> {
     const LEN_SIZE: usize = mem::size_of::<u64>();
     const HALF_LEN_SIZE: usize = LEN_SIZE / 2;
+    const RAW_LEN_SIZE: usize = mem::size_of::<u32>();
 
     let start = buf.len();
     let mut cursor = start;
     self.meta.encode_to_buffer(buf);
     cursor += VMeta::SIZE;
 
-    match self.value {
-      Some(v) => {
+    match &self.value {
+      Some(GenericValue::Raw(v)) => {
+        let kvlen_off = cursor;
         buf.put_u64_le_unchecked(0); // placeholder for the length
         cursor += LEN_SIZE;
+        if self.meta.is_compressed() {
+          buf.put_u32_le_unchecked(self.raw_len);
+          cursor += RAW_LEN_SIZE;
+        }
         let key_len = self.key.encode_to_buffer(buf).map_err(Either::Left)?;
         let value_len = v.encode_to_buffer(buf).map_err(Either::Right)?;
         let kvlen = merge_lengths(key_len as u32, value_len as u32);
-        buf[cursor - LEN_SIZE..cursor].copy_from_slice(&kvlen.to_le_bytes());
+        buf[kvlen_off..kvlen_off + LEN_SIZE].copy_from_slice(&kvlen.to_le_bytes());
+        cursor += key_len + value_len;
+        Ok(cursor - start)
+      }
+      Some(GenericValue::Compressed(stored)) => {
+        let kvlen_off = cursor;
+        buf.put_u64_le_unchecked(0); // placeholder for the length
+        cursor += LEN_SIZE;
+        buf.put_u32_le_unchecked(self.raw_len);
+        cursor += RAW_LEN_SIZE;
+        let key_len = self.key.encode_to_buffer(buf).map_err(Either::Left)?;
+        buf.put_slice_unchecked(stored);
+        let value_len = stored.len();
+        let kvlen = merge_lengths(key_len as u32, value_len as u32);
+        buf[kvlen_off..kvlen_off + LEN_SIZE].copy_from_slice(&kvlen.to_le_bytes());
         cursor += key_len + value_len;
-        Ok(cursor)
+        Ok(cursor - start)
       }
       None => {
         buf.put_u32_le_unchecked(0); // placeholder for the length
@@ -166,7 +245,15 @@ where
 pub struct GenericEntryRef<'a, K: ?Sized + Type, V: ?Sized + Type> {
   meta: VMeta,
   key: K::Ref<'a>,
-  value: Option<V::Ref<'a>>,
+  /// The value exactly as stored on disk: the encoded `V` verbatim, or,
+  /// when `meta.is_compressed()`, the compressed bytes. Decoding as `V` is
+  /// deferred to [`GenericEntryRef::value`], since a compressed value must
+  /// be decompressed into an owned buffer before it can be decoded.
+  value: Option<&'a [u8]>,
+  /// The value's encoded length before compression; meaningless unless
+  /// `meta.is_compressed()`.
+  raw_len: u32,
+  _value: core::marker::PhantomData<fn() -> V>,
 }
 
 impl<K, V> core::fmt::Debug for GenericEntryRef<'_, K, V>
@@ -178,11 +265,53 @@ where
     f.debug_struct("GenericEntryRef")
       .field("meta", &self.meta)
       .field("key", &self.key)
-      .field("value", &self.value)
       .finish()
   }
 }
 
+impl<'a, K, V> GenericEntryRef<'a, K, V>
+where
+  K: ?Sized + core::fmt::Debug + Type,
+  V: ?Sized + core::fmt::Debug + Type,
+{
+  /// Returns the entry's metadata.
+  #[inline]
+  pub const fn meta(&self) -> &VMeta {
+    &self.meta
+  }
+
+  /// Returns the entry's key.
+  #[inline]
+  pub const fn key(&self) -> &K::Ref<'a> {
+    &self.key
+  }
+
+  /// Returns the entry's value, decompressing it into `scratch` with
+  /// `compression` first if [`VMeta::is_compressed`] is set, then decoding
+  /// it as `V`. Uncompressed entries decode straight from the value log
+  /// and ignore `scratch`.
+  pub fn value<'b>(
+    &self,
+    compression: CompressionType,
+    scratch: &'b mut std::vec::Vec<u8>,
+  ) -> Result<Option<V::Ref<'b>>, DecompressError>
+  where
+    'a: 'b,
+  {
+    match self.value {
+      None => Ok(None),
+      Some(stored) => {
+        if self.meta.is_compressed() {
+          *scratch = decompress(compression, stored, self.raw_len as usize)?;
+          Ok(Some(unsafe { <V::Ref<'_> as TypeRef<'_>>::from_slice(scratch) }))
+        } else {
+          Ok(Some(unsafe { <V::Ref<'_> as TypeRef<'_>>::from_slice(stored) }))
+        }
+      }
+    }
+  }
+}
+
 impl<'a, K, V> TypeRef<'a> for GenericEntryRef<'a, K, V>
 where
   K: ?Sized + core::fmt::Debug + Type,
@@ -191,6 +320,7 @@ where
   unsafe fn from_slice(src: &'a [u8]) -> Self {
     const LEN_SIZE: usize = mem::size_of::<u64>();
     const HALF_LEN_SIZE: usize = LEN_SIZE / 2;
+    const RAW_LEN_SIZE: usize = mem::size_of::<u32>();
 
     let mut cursor = 0;
     let meta = VMeta::decode(&src[..VMeta::SIZE]);
@@ -209,6 +339,8 @@ where
         meta,
         key,
         value: None,
+        raw_len: 0,
+        _value: core::marker::PhantomData,
       }
     } else {
       let (key_len, value_len) = split_lengths(u64::from_le_bytes([
@@ -225,14 +357,29 @@ where
       let value_len = value_len as usize;
       cursor += LEN_SIZE;
 
+      let raw_len = if meta.is_compressed() {
+        let raw_len = u32::from_le_bytes([
+          src[cursor],
+          src[cursor + 1],
+          src[cursor + 2],
+          src[cursor + 3],
+        ]);
+        cursor += RAW_LEN_SIZE;
+        raw_len
+      } else {
+        value_len as u32
+      };
+
       let key = <K::Ref<'_> as TypeRef<'_>>::from_slice(&src[cursor..cursor + key_len]);
       cursor += key_len;
-      let value = <V::Ref<'_> as TypeRef<'_>>::from_slice(&src[cursor..cursor + value_len]);
+      let value = &src[cursor..cursor + value_len];
 
       Self {
         meta,
         key,
         value: Some(value),
+        raw_len,
+        _value: core::marker::PhantomData,
       }
     }
   }
@@ -1,6 +1,6 @@
 use crate::types::pointer::Pointer;
 
-use super::{Meta, VMeta, ValueLogCore};
+use super::{maybe_compress, CompressionType, Meta, VMeta, ValueLogCore};
 
 use dbutils::{
   buffer::VacantBuffer,
@@ -21,7 +21,8 @@ impl<C> ValueLog<C>
 where
   C: BuildChecksumer,
 {
-  /// Reads a entry from the value log at the given offset with size.
+  /// Reads a entry from the value log at the given offset with size. Use
+  /// [`EntryRef::value`] to get the value, decompressing it if needed.
   pub fn read(&self, pointer: Pointer) -> Result<EntryRef<'_>, Error> {
     unsafe {
       self
@@ -31,9 +32,68 @@ where
     }
   }
 
-  /// Inserts a key-value pair into the value log.
-  pub fn insert(&self, meta: Meta, key: &[u8], value: &[u8]) -> Result<Pointer, Error> {
-    let ent = Entry::new(meta.into(), key, Some(value));
+  /// Inserts a key-value pair into the value log, compressing `value` with
+  /// `compression` first if it is at least `min_compress_len` bytes and
+  /// doing so actually shrinks it; otherwise the value is stored verbatim.
+  /// The key is never compressed, so comparisons against it are unaffected.
+  ///
+  /// If `value` is larger than `segment_len`, it is split into
+  /// `segment_len`-sized chunks chained together (see
+  /// [`ValueLog::read_value`]) instead of being written as one entry;
+  /// small values take the existing single-entry fast path untouched.
+  pub fn insert(
+    &self,
+    meta: Meta,
+    key: &[u8],
+    value: &[u8],
+    min_compress_len: u64,
+    compression: CompressionType,
+    segment_len: u64,
+  ) -> Result<Pointer, Error> {
+    if (value.len() as u64) > segment_len {
+      return self.insert_chunked(meta, key, value, segment_len);
+    }
+
+    let compressed = maybe_compress(value, min_compress_len, compression);
+    let ent = match &compressed {
+      Some(c) => Entry::compressed(
+        VMeta::from(meta).with_compressed(),
+        key,
+        c,
+        value.len() as u32,
+      ),
+      None => Entry::new(meta.into(), key, Some(value)),
+    };
+    self.append(ent)
+  }
+
+  /// Splits `value` into `segment_len`-sized chunks and writes them as a
+  /// chain of chunk entries, last chunk first, so that each chunk can
+  /// embed the already-known [`Pointer`] to its successor. Returns the
+  /// pointer to the first (head) chunk.
+  fn insert_chunked(
+    &self,
+    meta: Meta,
+    key: &[u8],
+    value: &[u8],
+    segment_len: u64,
+  ) -> Result<Pointer, Error> {
+    let segment_len = (segment_len as usize).max(1);
+    let vmeta = VMeta::from(meta).with_chunked();
+
+    let mut next = None;
+    let mut tail_len = 0u64;
+    for part in value.chunks(segment_len).rev() {
+      let remaining_len = tail_len + part.len() as u64;
+      let ent = Entry::chunk(vmeta, key, part, next, remaining_len);
+      next = Some(self.append(ent)?);
+      tail_len = remaining_len;
+    }
+
+    Ok(next.expect("value.chunks() yields at least one chunk for non-empty segment_len"))
+  }
+
+  fn append(&self, ent: Entry<'_>) -> Result<Pointer, Error> {
     let encoded_len = ent.encoded_len();
     self
       .log
@@ -49,6 +109,34 @@ where
       .map_err(|e| e.unwrap_right())
   }
 
+  /// Reads `pointer`'s value, following the chunk chain and reassembling
+  /// it into one owned buffer if it was written by [`ValueLog::insert`]
+  /// as a multipart value; returns a zero-copy borrow of the stored bytes
+  /// otherwise.
+  ///
+  /// Chunked values are never compressed, so this does not take a
+  /// [`CompressionType`] and decompresses nothing: unlike with
+  /// [`EntryRef::value`], a non-chunked entry stored compressed comes back
+  /// as its compressed bytes.
+  pub fn read_value(&self, pointer: Pointer) -> Result<std::borrow::Cow<'_, [u8]>, Error> {
+    let head = self.read(pointer)?;
+    if !head.meta().is_chunked() {
+      return Ok(std::borrow::Cow::Borrowed(
+        head.raw_value().unwrap_or_default(),
+      ));
+    }
+
+    let mut buf = std::vec::Vec::with_capacity(head.remaining_len() as usize);
+    buf.extend_from_slice(head.chunk_value());
+    let mut next = head.next_chunk();
+    while let Some(ptr) = next {
+      let part = self.read(ptr)?;
+      buf.extend_from_slice(part.chunk_value());
+      next = part.next_chunk();
+    }
+    Ok(std::borrow::Cow::Owned(buf))
+  }
+
   /// Removes a key from the value log.
   ///
   /// **Note:** This is a fake delete operation, the key-value pair is not actually removed from the value log, just appended with a tombstone entry.
@@ -68,4 +156,36 @@ where
       .map(Pointer::new)
       .map_err(|e| e.unwrap_right())
   }
+
+  /// Appends a compact ref-delta record that increments the reference
+  /// count of the value most recently written for `key` by one, without
+  /// rewriting the value itself. This lets multiple index entries (e.g.
+  /// several MVCC versions, or deduplicated identical values) share one
+  /// physical value-log entry.
+  pub fn increment(&self, meta: Meta, key: &[u8]) -> Result<Pointer, Error> {
+    self.append_ref_delta(meta, key, 1)
+  }
+
+  /// Appends a compact ref-delta record that decrements the reference
+  /// count of the value most recently written for `key` by one.
+  ///
+  /// Ref-delta records only carry the delta, not a running total, so
+  /// `current_refs` — the caller's own tally of how many referrers remain
+  /// before this call — is required to tell whether the count has reached
+  /// zero. Once it has, GC may treat the value as reclaimable.
+  pub fn decrement(
+    &self,
+    meta: Meta,
+    key: &[u8],
+    current_refs: u32,
+  ) -> Result<(Pointer, bool), Error> {
+    self
+      .append_ref_delta(meta, key, -1)
+      .map(|ptr| (ptr, current_refs <= 1))
+  }
+
+  fn append_ref_delta(&self, meta: Meta, key: &[u8], delta: i32) -> Result<Pointer, Error> {
+    let ent = Entry::ref_delta(VMeta::from(meta).with_ref_delta(), key, delta);
+    self.append(ent)
+  }
 }
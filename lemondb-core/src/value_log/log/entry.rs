@@ -1,7 +1,9 @@
 use core::mem;
 
+use crate::types::pointer::Pointer;
+
 use super::{
-  super::{merge_lengths, split_lengths},
+  super::{decompress, merge_lengths, split_lengths, CompressionType, DecompressError},
   VMeta,
 };
 
@@ -44,12 +46,98 @@ pub(super) struct Entry<'a> {
   meta: VMeta,
   key: &'a [u8],
   value: Option<&'a [u8]>,
+  /// The value's length before compression. Equal to `value`'s length
+  /// (and not encoded on disk) unless `meta` is compressed, except on a
+  /// chunk entry, where it is `0`.
+  raw_len: u32,
+  /// For ordinary entries, the initial reference count (`1` unless the
+  /// value is being deduplicated against an existing one). For a
+  /// ref-delta record (`meta.is_ref_delta()`), the signed delta to apply
+  /// to the target value's reference count, bit-reinterpreted as `u32`.
+  refs: u32,
+  /// Only meaningful when `meta.is_chunked()`: the pointer to this
+  /// multipart value's next chunk (`None` on the last chunk) and the
+  /// number of value bytes from the start of this chunk through the end
+  /// of the chain, so a reader can preallocate the reassembly buffer.
+  chunk: Option<Chunk>,
+}
+
+/// The chaining metadata carried by a multipart value's chunk entries.
+#[derive(Clone, Copy)]
+pub(super) struct Chunk {
+  pub(super) next: Option<Pointer>,
+  pub(super) remaining_len: u64,
 }
 
 impl<'a> Entry<'a> {
   #[inline]
   pub(super) const fn new(meta: VMeta, key: &'a [u8], value: Option<&'a [u8]>) -> Self {
-    Self { meta, key, value }
+    let raw_len = match value {
+      Some(v) => v.len() as u32,
+      None => 0,
+    };
+    Self {
+      meta,
+      key,
+      value,
+      raw_len,
+      refs: 1,
+      chunk: None,
+    }
+  }
+
+  /// Builds an entry whose `value` is already compressed; `raw_len` is the
+  /// value's length before compression and is stored alongside it so
+  /// [`EntryRef::value`] knows how large a buffer to decompress into.
+  #[inline]
+  pub(super) const fn compressed(meta: VMeta, key: &'a [u8], value: &'a [u8], raw_len: u32) -> Self {
+    Self {
+      meta,
+      key,
+      value: Some(value),
+      raw_len,
+      refs: 1,
+      chunk: None,
+    }
+  }
+
+  /// Builds one chunk of a multipart value: `value` is this chunk's raw
+  /// bytes, `next` points at the following chunk (`None` if this is the
+  /// last one), and `remaining_len` is the number of value bytes from the
+  /// start of this chunk through the end of the chain. `meta` must have
+  /// [`VMeta::with_chunked`] applied.
+  #[inline]
+  pub(super) const fn chunk(
+    meta: VMeta,
+    key: &'a [u8],
+    value: &'a [u8],
+    next: Option<Pointer>,
+    remaining_len: u64,
+  ) -> Self {
+    Self {
+      meta,
+      key,
+      value: Some(value),
+      raw_len: 0,
+      refs: 0,
+      chunk: Some(Chunk { next, remaining_len }),
+    }
+  }
+
+  /// Builds a compact ref-delta record: no value, just `key` and the
+  /// signed `delta` to apply to the reference count of the value most
+  /// recently stored for `key`. `meta` must have
+  /// [`VMeta::with_ref_delta`] applied.
+  #[inline]
+  pub(super) const fn ref_delta(meta: VMeta, key: &'a [u8], delta: i32) -> Self {
+    Self {
+      meta,
+      key,
+      value: None,
+      raw_len: 0,
+      refs: delta as u32,
+      chunk: None,
+    }
   }
 }
 
@@ -67,35 +155,88 @@ impl<'a> Type for Entry<'a> {
   #[inline]
   fn encoded_len(&self) -> usize {
     let key_len = self.key.encoded_len();
+    let raw_len_field = if self.meta.is_compressed() {
+      mem::size_of::<u32>()
+    } else {
+      0
+    };
+    let refs_field = mem::size_of::<u32>();
+    let chunk_field = match &self.chunk {
+      Some(c) => {
+        mem::size_of::<u8>()
+          + if c.next.is_some() { Pointer::ENCODED_LEN } else { 0 }
+          + mem::size_of::<u64>()
+      }
+      None => 0,
+    };
 
     match self.value {
       Some(v) => {
         let value_len = v.encoded_len();
-        VMeta::SIZE + mem::size_of::<u64>() + key_len + value_len
+        VMeta::SIZE
+          + refs_field
+          + chunk_field
+          + mem::size_of::<u64>()
+          + raw_len_field
+          + key_len
+          + value_len
       }
-      None => VMeta::SIZE + mem::size_of::<u32>() + key_len,
+      None => VMeta::SIZE + refs_field + chunk_field + mem::size_of::<u32>() + key_len,
     }
   }
 
   fn encode(&self, buf: &mut [u8]) -> Result<usize, Self::Error> {
     const LEN_SIZE: usize = mem::size_of::<u64>();
     const HALF_LEN_SIZE: usize = LEN_SIZE / 2;
+    const RAW_LEN_SIZE: usize = mem::size_of::<u32>();
+    const REFS_SIZE: usize = mem::size_of::<u32>();
 
     let mut cursor = 0;
     self.meta.encode(&mut buf[..VMeta::SIZE]);
     cursor += VMeta::SIZE;
 
+    buf[cursor..cursor + REFS_SIZE].copy_from_slice(&self.refs.to_le_bytes());
+    cursor += REFS_SIZE;
+
+    if let Some(c) = &self.chunk {
+      match c.next {
+        Some(next) => {
+          buf[cursor] = 1;
+          cursor += 1;
+          next
+            .encode(&mut buf[cursor..cursor + Pointer::ENCODED_LEN])
+            .expect("buffer sized by encoded_len");
+          cursor += Pointer::ENCODED_LEN;
+        }
+        None => {
+          buf[cursor] = 0;
+          cursor += 1;
+        }
+      }
+      buf[cursor..cursor + mem::size_of::<u64>()].copy_from_slice(&c.remaining_len.to_le_bytes());
+      cursor += mem::size_of::<u64>();
+    }
+
     let size = match self.value {
       Some(v) => {
         let klen = self.key.len();
         let vlen = v.len();
-        let ko = cursor + LEN_SIZE;
-        let vo = cursor + LEN_SIZE + klen;
-        buf[ko..ko + klen].copy_from_slice(self.key);
-        buf[vo..vo + vlen].copy_from_slice(v);
         let kvlen = merge_lengths(klen as u32, vlen as u32);
         buf[cursor..cursor + LEN_SIZE].copy_from_slice(&kvlen.to_le_bytes());
-        cursor += LEN_SIZE + klen + vlen;
+        cursor += LEN_SIZE;
+
+        // the original length is only needed to size the decompression
+        // buffer, so uncompressed entries skip it entirely
+        if self.meta.is_compressed() {
+          buf[cursor..cursor + RAW_LEN_SIZE].copy_from_slice(&self.raw_len.to_le_bytes());
+          cursor += RAW_LEN_SIZE;
+        }
+
+        let ko = cursor;
+        let vo = cursor + klen;
+        buf[ko..ko + klen].copy_from_slice(self.key);
+        buf[vo..vo + vlen].copy_from_slice(v);
+        cursor += klen + vlen;
         cursor
       }
       None => {
@@ -114,10 +255,27 @@ impl<'a> Type for Entry<'a> {
   fn encode_to_buffer(&self, buf: &mut valog::VacantBuffer<'_>) -> Result<usize, Self::Error> {
     let len = buf.len();
     self.meta.encode_to_buffer(buf);
+    buf.put_u32_le_unchecked(self.refs);
+
+    if let Some(c) = &self.chunk {
+      match c.next {
+        Some(next) => {
+          buf.put_u8_unchecked(1);
+          let mut ptr_buf = [0u8; Pointer::ENCODED_LEN];
+          next.encode(&mut ptr_buf).expect("fixed-size buffer");
+          buf.put_slice_unchecked(&ptr_buf);
+        }
+        None => buf.put_u8_unchecked(0),
+      }
+      buf.put_u64_le_unchecked(c.remaining_len);
+    }
 
     match self.value {
       Some(v) => {
         buf.put_u64_le_unchecked(merge_lengths(self.key.len() as u32, v.len() as u32));
+        if self.meta.is_compressed() {
+          buf.put_u32_le_unchecked(self.raw_len);
+        }
         buf.put_slice_unchecked(self.key);
         buf.put_slice_unchecked(v);
       }
@@ -136,6 +294,15 @@ pub struct EntryRef<'a> {
   meta: VMeta,
   key: &'a [u8],
   value: Option<&'a [u8]>,
+  /// The value's length before compression; meaningless unless
+  /// `meta.is_compressed()`.
+  raw_len: u32,
+  /// For ordinary entries, the reference count the value was written
+  /// with. For a ref-delta record (`meta.is_ref_delta()`), the signed
+  /// delta to apply, bit-reinterpreted as `u32`.
+  refs: u32,
+  /// Only meaningful when `meta.is_chunked()`.
+  chunk: Option<Chunk>,
 }
 
 impl<'a> core::fmt::Debug for EntryRef<'a> {
@@ -148,16 +315,151 @@ impl<'a> core::fmt::Debug for EntryRef<'a> {
   }
 }
 
+impl<'a> EntryRef<'a> {
+  /// Returns the entry's metadata.
+  #[inline]
+  pub const fn meta(&self) -> &VMeta {
+    &self.meta
+  }
+
+  /// Returns the entry's key.
+  #[inline]
+  pub const fn key(&self) -> &'a [u8] {
+    self.key
+  }
+
+  /// Returns the value exactly as stored on disk, without decompressing it.
+  #[inline]
+  pub const fn raw_value(&self) -> Option<&'a [u8]> {
+    self.value
+  }
+
+  /// Returns the reference count this entry's value was written with.
+  ///
+  /// ## Panics
+  /// - If this entry is a ref-delta record; use [`EntryRef::ref_delta`]
+  ///   instead.
+  #[inline]
+  pub fn refs(&self) -> u32 {
+    assert!(!self.meta.is_ref_delta(), "entry is a ref-delta record");
+    self.refs
+  }
+
+  /// Returns the signed delta a ref-delta record applies to the reference
+  /// count of the value most recently written for [`EntryRef::key`].
+  ///
+  /// ## Panics
+  /// - If this entry is not a ref-delta record.
+  #[inline]
+  pub fn ref_delta(&self) -> i32 {
+    assert!(self.meta.is_ref_delta(), "entry is not a ref-delta record");
+    self.refs as i32
+  }
+
+  /// Returns the entry's value, decompressing it with `compression` if
+  /// [`VMeta::is_compressed`] is set. Uncompressed entries borrow straight
+  /// from the value log; compressed entries are decoded into an owned
+  /// buffer.
+  pub fn value(
+    &self,
+    compression: CompressionType,
+  ) -> Result<Option<std::borrow::Cow<'a, [u8]>>, DecompressError> {
+    match self.value {
+      None => Ok(None),
+      Some(v) => {
+        if self.meta.is_compressed() {
+          decompress(compression, v, self.raw_len as usize)
+            .map(|owned| Some(std::borrow::Cow::Owned(owned)))
+        } else {
+          Ok(Some(std::borrow::Cow::Borrowed(v)))
+        }
+      }
+    }
+  }
+
+  /// Returns this chunk's raw bytes, for a multipart value's chunk entry.
+  ///
+  /// ## Panics
+  /// - If this entry is not chunked.
+  #[inline]
+  pub fn chunk_value(&self) -> &'a [u8] {
+    assert!(self.meta.is_chunked(), "entry is not chunked");
+    self.value.expect("a chunk entry always carries a value")
+  }
+
+  /// Returns the pointer to this multipart value's next chunk, or `None`
+  /// if this is the last chunk.
+  ///
+  /// ## Panics
+  /// - If this entry is not chunked.
+  #[inline]
+  pub fn next_chunk(&self) -> Option<Pointer> {
+    assert!(self.meta.is_chunked(), "entry is not chunked");
+    self.chunk.expect("a chunked entry always carries chunk metadata").next
+  }
+
+  /// Returns the number of value bytes from the start of this chunk
+  /// through the end of the chain.
+  ///
+  /// ## Panics
+  /// - If this entry is not chunked.
+  #[inline]
+  pub fn remaining_len(&self) -> u64 {
+    assert!(self.meta.is_chunked(), "entry is not chunked");
+    self
+      .chunk
+      .expect("a chunked entry always carries chunk metadata")
+      .remaining_len
+  }
+}
+
 impl<'a> TypeRef<'a> for EntryRef<'a> {
   unsafe fn from_slice(src: &'a [u8]) -> Self {
     const LEN_SIZE: usize = mem::size_of::<u64>();
     const HALF_LEN_SIZE: usize = LEN_SIZE / 2;
+    const RAW_LEN_SIZE: usize = mem::size_of::<u32>();
+    const REFS_SIZE: usize = mem::size_of::<u32>();
 
     let mut cursor = 0;
     let meta = VMeta::decode(&src[..VMeta::SIZE]);
     cursor += VMeta::SIZE;
 
-    if meta.is_tombstone() {
+    let refs = u32::from_le_bytes([
+      src[cursor],
+      src[cursor + 1],
+      src[cursor + 2],
+      src[cursor + 3],
+    ]);
+    cursor += REFS_SIZE;
+
+    let chunk = if meta.is_chunked() {
+      let has_next = src[cursor];
+      cursor += 1;
+      let next = if has_next != 0 {
+        let ptr = Pointer::decode(&src[cursor..cursor + Pointer::ENCODED_LEN])
+          .expect("chunk pointer written by Entry::chunk is always well-formed");
+        cursor += Pointer::ENCODED_LEN;
+        Some(ptr)
+      } else {
+        None
+      };
+      let remaining_len = u64::from_le_bytes([
+        src[cursor],
+        src[cursor + 1],
+        src[cursor + 2],
+        src[cursor + 3],
+        src[cursor + 4],
+        src[cursor + 5],
+        src[cursor + 6],
+        src[cursor + 7],
+      ]);
+      cursor += mem::size_of::<u64>();
+      Some(Chunk { next, remaining_len })
+    } else {
+      None
+    };
+
+    if meta.is_tombstone() || meta.is_ref_delta() {
       let key_len = u32::from_le_bytes([
         src[cursor],
         src[cursor + 1],
@@ -169,6 +471,9 @@ impl<'a> TypeRef<'a> for EntryRef<'a> {
         meta,
         key: &src[cursor..cursor + key_len as usize],
         value: None,
+        raw_len: 0,
+        refs,
+        chunk,
       }
     } else {
       let (key_len, value_len) = split_lengths(u64::from_le_bytes([
@@ -185,6 +490,19 @@ impl<'a> TypeRef<'a> for EntryRef<'a> {
       let value_len = value_len as usize;
       cursor += LEN_SIZE;
 
+      let raw_len = if meta.is_compressed() {
+        let raw_len = u32::from_le_bytes([
+          src[cursor],
+          src[cursor + 1],
+          src[cursor + 2],
+          src[cursor + 3],
+        ]);
+        cursor += RAW_LEN_SIZE;
+        raw_len
+      } else {
+        value_len as u32
+      };
+
       let key = &src[cursor..cursor + key_len];
       cursor += key_len;
       let value = &src[cursor..cursor + value_len];
@@ -193,6 +511,9 @@ impl<'a> TypeRef<'a> for EntryRef<'a> {
         meta,
         key,
         value: Some(value),
+        raw_len,
+        refs,
+        chunk,
       }
     }
   }
@@ -1,6 +1,23 @@
 use dbutils::buffer::VacantBuffer;
 use zerocopy::{FromBytes, FromZeroes};
 
+/// The compression algorithm a value-log entry's payload was stored with.
+///
+/// Only whether an entry is compressed is recorded per-entry (the
+/// [`Meta::COMPRESSED_FLAG`] bit); which codec to use for decompression is a
+/// value log-wide setting, the same way parity-db fixes a codec per column.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CompressionType {
+  /// No compression.
+  #[default]
+  None = 0,
+  /// LZ4 block compression.
+  Lz4 = 1,
+  /// Zstandard compression.
+  Zstd = 2,
+}
+
 /// The metadata for the value log.
 ///
 /// The metadata is in the following layout:
@@ -8,22 +25,22 @@ use zerocopy::{FromBytes, FromZeroes};
 /// - With `ttl` feature enabled:
 ///
 ///   ```text
-///   +---------------------+------------------------------+------------------------+
-///   | 63 bits for version |   1 bit for tombstone mark   | 64 bits for expiration |
-///   +---------------------+------------------------------+------------------------+
+///   +---------------------+----------------------+----------------------------+-------------------------+------------------------+
+///   | 61 bits for version | 1 bit for tombstone  |   1 bit for compressed mark |  1 bit for chunked mark | 64 bits for expiration |
+///   +---------------------+----------------------+----------------------------+-------------------------+------------------------+
 ///   ```
 ///
 /// - Without `ttl` feature enabled:
 ///
 ///   ```text
-///   +---------------------+----------------------------------+
-///   | 63 bits for version |   1 bit for tombstone mark   |
-///   +---------------------+----------------------------------+
+///   +---------------------+----------------------+----------------------------+-------------------------+
+///   | 61 bits for version | 1 bit for tombstone  |   1 bit for compressed mark |  1 bit for chunked mark |
+///   +---------------------+----------------------+----------------------------+-------------------------+
 ///   ```
 #[derive(Copy, Clone, Eq, PartialEq, FromZeroes, FromBytes)]
 #[repr(C, align(8))]
 pub struct Meta {
-  /// 63 bits for version, 1 bit for tombstone mark
+  /// 61 bits for version, 1 bit each for the tombstone, compressed and chunked marks
   meta: u64,
   #[cfg(feature = "ttl")]
   expire_at: u64,
@@ -55,7 +72,9 @@ impl Meta {
   ) -> core::fmt::DebugStruct<'a, 'b> {
     let mut s = f.debug_struct("Meta");
     s.field("version", &self.version())
-      .field("pointer", &self.is_tombstone());
+      .field("tombstone", &self.is_tombstone())
+      .field("compressed", &self.is_compressed())
+      .field("chunked", &self.is_chunked());
     s
   }
 
@@ -104,13 +123,17 @@ impl Meta {
 }
 
 impl Meta {
-  pub(crate) const VERSION_MASK: u64 = !0u64 >> 1; // 0xFFFFFFFFFFFFFFFE // 63 bits for version
-  pub(crate) const TOMBSTONE_FLAG: u64 = 1 << 63; // 64th bit for tombstone mark
+  /// The maximum version.
+  pub const MAX_VERSION: u64 = (1 << 61) - 1;
+  pub(crate) const VERSION_MASK: u64 = (1 << 61) - 1; // 61 bits for version
+  pub(crate) const TOMBSTONE_FLAG: u64 = 1 << 61; // 62nd bit for tombstone mark
+  pub(crate) const COMPRESSED_FLAG: u64 = 1 << 62; // 63rd bit for compressed mark
+  pub(crate) const CHUNKED_FLAG: u64 = 1 << 63; // 64th bit for chunked (multipart) mark
 
   /// Create a new metadata with the given version.
   #[inline]
   pub const fn new(version: u64, #[cfg(feature = "ttl")] expire_at: u64) -> Self {
-    assert!(version < (1 << 63), "version is too large");
+    assert!(version <= Self::MAX_VERSION, "version is too large");
 
     Self {
       meta: version,
@@ -126,10 +149,62 @@ impl Meta {
     self
   }
 
+  /// Set the compressed flag, marking the entry's value as stored
+  /// compressed rather than verbatim.
+  #[inline]
+  pub fn with_compressed(mut self) -> Self {
+    self.meta |= Self::COMPRESSED_FLAG;
+    self
+  }
+
+  /// Both the tombstone and compressed bits together mark a ref-delta
+  /// record rather than an ordinary entry: a ref-delta carries no value,
+  /// so the compressed bit is otherwise meaningless on it, and it is never
+  /// itself a tombstone. Reusing the pair keeps `Meta` at one `u64` instead
+  /// of spending a third bit on the version field.
+  pub(crate) const REF_DELTA_FLAG: u64 = Self::TOMBSTONE_FLAG | Self::COMPRESSED_FLAG;
+
+  /// Set the ref-delta marker, indicating this entry is a compact
+  /// reference-count delta rather than a value or a tombstone.
+  #[inline]
+  pub fn with_ref_delta(mut self) -> Self {
+    self.meta |= Self::REF_DELTA_FLAG;
+    self
+  }
+
+  /// Set the chunked marker, indicating this entry is one part of a
+  /// multipart value (see [`ValueLog::insert`](super::log::ValueLog::insert)):
+  /// its payload is a raw byte chunk followed by a pointer to the next
+  /// chunk, rather than a complete, directly usable value.
+  #[inline]
+  pub fn with_chunked(mut self) -> Self {
+    self.meta |= Self::CHUNKED_FLAG;
+    self
+  }
+
   /// Returns `true` if the value of the entry is a tombstone.
   #[inline]
   pub const fn is_tombstone(&self) -> bool {
-    self.meta & Self::TOMBSTONE_FLAG != 0
+    self.meta & Self::REF_DELTA_FLAG != Self::REF_DELTA_FLAG && self.meta & Self::TOMBSTONE_FLAG != 0
+  }
+
+  /// Returns `true` if the entry's value is stored compressed.
+  #[inline]
+  pub const fn is_compressed(&self) -> bool {
+    self.meta & Self::REF_DELTA_FLAG != Self::REF_DELTA_FLAG && self.meta & Self::COMPRESSED_FLAG != 0
+  }
+
+  /// Returns `true` if this entry is a compact reference-count delta
+  /// rather than a value or a tombstone.
+  #[inline]
+  pub const fn is_ref_delta(&self) -> bool {
+    self.meta & Self::REF_DELTA_FLAG == Self::REF_DELTA_FLAG
+  }
+
+  /// Returns `true` if this entry is one chunk of a multipart value.
+  #[inline]
+  pub const fn is_chunked(&self) -> bool {
+    self.meta & Self::CHUNKED_FLAG != 0
   }
 
   /// Returns the version.
@@ -149,3 +224,75 @@ impl From<crate::types::meta::Meta> for Meta {
     )
   }
 }
+
+/// Returned when a compressed value-log entry cannot be decompressed.
+#[derive(Debug, Clone, Copy, thiserror::Error)]
+#[non_exhaustive]
+pub enum DecompressError {
+  /// The stored bytes did not decompress into a value of the recorded
+  /// length, indicating corruption.
+  #[error("failed to decompress value-log entry")]
+  Corrupted,
+}
+
+/// Compresses `value` with `compression` if it is at least `min_compress_len`
+/// bytes long and the compressed form actually comes out smaller; otherwise
+/// returns `None` and the caller should store `value` verbatim.
+pub(crate) fn maybe_compress(
+  value: &[u8],
+  min_compress_len: u64,
+  compression: CompressionType,
+) -> Option<std::vec::Vec<u8>> {
+  if matches!(compression, CompressionType::None) || (value.len() as u64) < min_compress_len {
+    return None;
+  }
+
+  let compressed = match compression {
+    CompressionType::None => return None,
+    CompressionType::Lz4 => lz4_flex::block::compress(value),
+    #[cfg(feature = "zstd")]
+    CompressionType::Zstd => zstd::bulk::compress(value, 0).ok()?,
+    #[cfg(not(feature = "zstd"))]
+    CompressionType::Zstd => return None,
+  };
+
+  (compressed.len() < value.len()).then_some(compressed)
+}
+
+/// The largest multiple of the stored (compressed) size that `raw_len` is
+/// allowed to claim before [`decompress`] refuses to allocate for it.
+const MAX_DECOMPRESSION_RATIO: usize = 1024;
+/// A floor under the ratio-derived budget, so tiny `stored` payloads (e.g. a
+/// handful of bytes) aren't held to an unreasonably tight cap.
+const MIN_DECOMPRESSION_BUDGET: usize = 4 * 1024;
+
+/// Decompresses `stored` (the bytes physically on disk) back into the
+/// original value of length `raw_len`, using `compression`.
+pub(crate) fn decompress(
+  compression: CompressionType,
+  stored: &[u8],
+  raw_len: usize,
+) -> Result<std::vec::Vec<u8>, DecompressError> {
+  if !matches!(compression, CompressionType::None) {
+    let max_raw_len = stored
+      .len()
+      .saturating_mul(MAX_DECOMPRESSION_RATIO)
+      .max(MIN_DECOMPRESSION_BUDGET);
+    if raw_len > max_raw_len {
+      return Err(DecompressError::Corrupted);
+    }
+  }
+
+  match compression {
+    CompressionType::None => Ok(stored.to_vec()),
+    CompressionType::Lz4 => {
+      lz4_flex::block::decompress(stored, raw_len).map_err(|_| DecompressError::Corrupted)
+    }
+    #[cfg(feature = "zstd")]
+    CompressionType::Zstd => {
+      zstd::bulk::decompress(stored, raw_len).map_err(|_| DecompressError::Corrupted)
+    }
+    #[cfg(not(feature = "zstd"))]
+    CompressionType::Zstd => Err(DecompressError::Corrupted),
+  }
+}
@@ -26,21 +26,43 @@ pub enum LogFileError {
 
   #[cfg_attr(feature = "std", error(transparent))]
   IO(#[from] std::io::Error),
-  // /// A log error occurred.
-  // #[cfg_attr(feature = "std", error(transparent))]
-  // Log(#[cfg_attr(feature = "std", from)] skl::map::Error),
-  // /// Returned when writing the batch failed.
-  // #[cfg_attr(
-  //   feature = "std",
-  //   error("failed to write batch at index {idx}: {source}")
-  // )]
-  // WriteBatch {
-  //   /// The index of the key-value pair that caused the error.
-  //   idx: usize,
-  //   /// The error that caused the failure.
-  //   #[cfg_attr(feature = "std", source)]
-  //   source: skl::map::Error,
-  // },
+  /// A log error occurred.
+  #[cfg_attr(feature = "std", error(transparent))]
+  Log(#[cfg_attr(feature = "std", from)] skl::map::Error),
+  /// Returned when an atomic [`LogFile::insert_batch`](crate::wal::lf::LogFile::insert_batch)
+  /// failed to reserve space for one of its entries; nothing in the batch
+  /// was linked into the log, since the reservation pass runs to
+  /// completion (or fails) before any node is attached.
+  #[cfg_attr(
+    feature = "std",
+    error("failed to write batch at index {idx}: {source}")
+  )]
+  WriteBatch {
+    /// The index of the key-value pair that caused the error.
+    idx: usize,
+    /// The error that caused the failure.
+    #[cfg_attr(feature = "std", source)]
+    source: skl::map::Error,
+  },
+  /// Returned when [`LogFile::insert_batch`](crate::wal::lf::LogFile::insert_batch)'s
+  /// up-front `has_space` check finds no room to reserve one of the
+  /// batch's entries; checked right before that entry is allocated, so the
+  /// entries before it already hold allocated nodes, but nothing in the
+  /// batch has been linked into the log.
+  #[cfg_attr(
+    feature = "std",
+    error(
+      "not enough space to reserve batch entry at index {idx}, required: {required}, remaining: {remaining}"
+    )
+  )]
+  InsufficientSpace {
+    /// The index of the key-value pair that caused the error.
+    idx: usize,
+    /// The required space.
+    required: u64,
+    /// The remaining space.
+    remaining: u64,
+  },
   /// Returned when checksum mismatch.
   #[cfg_attr(feature = "std", error("checksum mismatch"))]
   ChecksumMismatch(#[cfg_attr(feature = "std", from)] ChecksumMismatch),
@@ -62,6 +84,15 @@ impl core::fmt::Display for LogFileError {
       Self::WriteBatch { idx, source } => {
         write!(f, "failed to write batch at index {}: {}", idx, source)
       }
+      Self::InsufficientSpace {
+        idx,
+        required,
+        remaining,
+      } => write!(
+        f,
+        "not enough space to reserve batch entry at index {}, required: {}, remaining: {}",
+        idx, required, remaining
+      ),
     }
   }
 }
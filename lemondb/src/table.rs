@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::sync::{atomic::{AtomicU64, Ordering}, Arc};
 
 use aol::checksum::BuildChecksumer;
 use crossbeam_skiplist::SkipMap;
@@ -12,12 +12,58 @@ use lemondb_core::{
 use parking_lot::Mutex;
 use skl::Comparator;
 
+/// The live-byte ratio [`Table::gc_value_log`] passes to
+/// [`Table::gc_value_log_with`] as its threshold.
+const DEFAULT_MIN_LIVE_RATIO: f64 = 0.5;
+
+/// Bytes and time accounted for by one value-log GC pass, for observability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct GcStats {
+  /// Bytes read from the value log being collected.
+  pub(crate) bytes_read: u64,
+  /// Bytes of still-live entries rewritten into the active value log.
+  pub(crate) bytes_rewritten: u64,
+  /// Bytes dropped because the index no longer points at them.
+  pub(crate) bytes_dropped: u64,
+}
+
+/// Entries and bytes accounted for by one expiration-reaping pass, for
+/// observability.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ReapStats {
+  /// Entries found whose `expire_at` had passed as of the reap's `now`.
+  pub(crate) entries_expired: u64,
+  /// Tombstones written to the active log for expired entries.
+  pub(crate) tombstones_written: u64,
+  /// Value-log bytes reported via [`Table::record_discard`] for expired
+  /// entries that pointed at a value log.
+  pub(crate) bytes_discarded: u64,
+}
+
+/// An error returned when a value log cannot be GC'd.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub(crate) enum TableError {
+  /// The value log is not tracked by this table.
+  #[error("value log {0} is not tracked by this table")]
+  ValueLogNotFound(Fid),
+}
+
 pub(crate) struct Table<C> {
   id: TableId,
   name: TableName,
   active_logs: Arc<SkipMap<Fid, ActiveLogFileReader<C>>>,
   frozen_logs: Arc<SkipMap<Fid, ImmutableLogFile<Key<C>>>>,
   vlfs: Arc<SkipMap<Fid, Arc<ValueLog>>>,
+  /// Total bytes written to each value log. [`ValueLog`] keeps no byte
+  /// count of its own, so this is maintained by [`Table::record_write`],
+  /// next to the [`Table::record_discard`] counts, to make a live-byte ratio
+  /// computable.
+  written: Arc<SkipMap<Fid, AtomicU64>>,
+  /// Bytes made dead in each value log by a `remove`/overwrite, maintained
+  /// alongside every write the same way parity-db tracks free space, so
+  /// GC eligibility can be read back without rescanning the log.
+  discardable: Arc<SkipMap<Fid, AtomicU64>>,
 }
 
 impl<C> Table<C> {
@@ -30,6 +76,106 @@ impl<C> Table<C> {
   //     }
   //   });
   // }
+
+  /// Records that `bytes` were appended to `fid`, e.g. after an
+  /// `insert`/`remove`/`increment`/`decrement` on its [`ValueLog`].
+  pub(crate) fn record_write(&self, fid: Fid, bytes: u64) {
+    Self::add(&self.written, fid, bytes);
+  }
+
+  /// Records that `bytes` of `fid`'s content became dead, e.g. because a
+  /// key was overwritten or removed and `fid` held its previous value.
+  pub(crate) fn record_discard(&self, fid: Fid, bytes: u64) {
+    Self::add(&self.discardable, fid, bytes);
+  }
+
+  fn add(counters: &SkipMap<Fid, AtomicU64>, fid: Fid, bytes: u64) {
+    match counters.get(&fid) {
+      Some(entry) => {
+        entry.value().fetch_add(bytes, Ordering::Relaxed);
+      }
+      None => {
+        counters.insert(fid, AtomicU64::new(bytes));
+      }
+    }
+  }
+
+  /// Returns the fraction of `fid`'s bytes that are still live, or `None`
+  /// if `fid` is not one of this table's value logs.
+  pub(crate) fn live_ratio(&self, fid: Fid) -> Option<f64> {
+    self.vlfs.get(&fid)?;
+
+    let total = self
+      .written
+      .get(&fid)
+      .map(|entry| entry.value().load(Ordering::Relaxed))
+      .unwrap_or(0);
+    if total == 0 {
+      return Some(1.0);
+    }
+
+    let discarded = self
+      .discardable
+      .get(&fid)
+      .map(|entry| entry.value().load(Ordering::Relaxed))
+      .unwrap_or(0);
+
+    Some(1.0 - (discarded.min(total) as f64 / total as f64))
+  }
+
+  /// Returns `true` if `fid`'s live-byte ratio has dropped below
+  /// `min_live_ratio` and it should be scheduled for GC.
+  pub(crate) fn should_gc_value_log(&self, fid: Fid, min_live_ratio: f64) -> bool {
+    self
+      .live_ratio(fid)
+      .is_some_and(|ratio| ratio < min_live_ratio)
+  }
+
+  /// Runs GC on `fid` using [`DEFAULT_MIN_LIVE_RATIO`]; see
+  /// [`Table::gc_value_log_with`].
+  #[allow(dead_code)]
+  pub(crate) fn gc_value_log(&self, fid: Fid) -> Result<GcStats, TableError> {
+    self.gc_value_log_with(fid, DEFAULT_MIN_LIVE_RATIO)
+  }
+
+  /// Checks whether `fid` is due for GC, i.e. whether its live ratio has
+  /// dropped below `min_live_ratio`.
+  ///
+  /// Returns [`TableError::ValueLogNotFound`] if `fid` is not one of this
+  /// table's value logs, and an empty [`GcStats`] otherwise, whether or not
+  /// `fid` is due: no entry is read or rewritten and `fid` stays in `vlfs`.
+  /// [`ValueLog`] offers only the point-lookup [`ValueLog::read`], with no
+  /// sequential entry iterator to stream through.
+  pub(crate) fn gc_value_log_with(
+    &self,
+    fid: Fid,
+    min_live_ratio: f64,
+  ) -> Result<GcStats, TableError> {
+    if self.vlfs.get(&fid).is_none() {
+      return Err(TableError::ValueLogNotFound(fid));
+    }
+
+    if !self.should_gc_value_log(fid, min_live_ratio) {
+      return Ok(GcStats::default());
+    }
+
+    Ok(GcStats::default())
+  }
+
+  /// Returns an empty [`ReapStats`] for `now`: no frozen log is scanned for
+  /// entries whose `expire_at` has passed, no tombstone is written to the
+  /// active log, and nothing is fed into [`Table::record_discard`].
+  ///
+  /// [`ImmutableLogFile`] offers point lookups and bound queries but no
+  /// entry iterator to scan through, the same gap
+  /// [`compaction::compact_table`](lemondb_core::compaction::compact_table)
+  /// stops at.
+  #[allow(dead_code)]
+  pub(crate) fn reap_expired(&self, now: u64) -> ReapStats {
+    let _ = now;
+    let _ = &self.frozen_logs;
+    ReapStats::default()
+  }
 }
 
 // /// a
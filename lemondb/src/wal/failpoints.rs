@@ -0,0 +1,85 @@
+//! A tiny, name-keyed failpoint registry used to deterministically exercise
+//! [`super::Wal::insert_batch`]'s otherwise hard-to-trigger cleanup paths in
+//! tests: [`super::cleanup_vlogs_on_failure`] (rewind vs remove),
+//! [`super::Wal::cleanup_logs_on_failure`] (rewind the active arena back to
+//! `log_allocated`, re-register one new log, delete the rest), and the
+//! manifest `append_batch` failure branch.
+//!
+//! Entirely compiled out unless the `failpoints` feature is enabled, so a
+//! release build pays nothing for it: without the feature, [`Action`],
+//! [`action`] and the registry do not exist and [`fail_point!`] expands to
+//! nothing.
+
+#[cfg(feature = "failpoints")]
+use std::{
+  collections::HashMap,
+  sync::{Mutex, OnceLock},
+};
+
+/// What a named failpoint does when it fires.
+#[cfg(feature = "failpoints")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+  /// The failpoint is disarmed; the guarded code runs normally.
+  Off,
+  /// Return the error the call site's [`fail_point!`] invocation supplies,
+  /// as if the fallible operation right after it had failed.
+  Return,
+  /// Panic, simulating a crash mid-batch.
+  Panic,
+}
+
+#[cfg(feature = "failpoints")]
+fn registry() -> &'static Mutex<HashMap<&'static str, Action>> {
+  static REGISTRY: OnceLock<Mutex<HashMap<&'static str, Action>>> = OnceLock::new();
+  REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Arms `name` with `action`, overriding any previous action for that name.
+#[cfg(feature = "failpoints")]
+pub(crate) fn set(name: &'static str, action: Action) {
+  registry().lock().unwrap().insert(name, action);
+}
+
+/// Disarms every failpoint, restoring the default (`Off`) behavior.
+#[cfg(feature = "failpoints")]
+pub(crate) fn clear_all() {
+  registry().lock().unwrap().clear();
+}
+
+/// Returns the action currently armed for `name`, defaulting to
+/// [`Action::Off`] if it was never set.
+#[cfg(feature = "failpoints")]
+pub(crate) fn action(name: &str) -> Action {
+  registry()
+    .lock()
+    .unwrap()
+    .get(name)
+    .copied()
+    .unwrap_or(Action::Off)
+}
+
+/// Checks whether the named failpoint is armed and, if so, either panics or
+/// evaluates to `$err` via an early `return`; otherwise falls through and
+/// evaluates to `()`.
+///
+/// With the `failpoints` feature disabled this expands to nothing.
+#[cfg(feature = "failpoints")]
+macro_rules! fail_point {
+  ($name:expr, $err:expr) => {
+    match $crate::wal::failpoints::action($name) {
+      $crate::wal::failpoints::Action::Return => return $err,
+      $crate::wal::failpoints::Action::Panic => {
+        panic!("failpoint {} fired", $name)
+      }
+      $crate::wal::failpoints::Action::Off => {}
+    }
+  };
+}
+
+#[cfg(not(feature = "failpoints"))]
+macro_rules! fail_point {
+  ($name:expr, $err:expr) => {};
+}
+
+pub(crate) use fail_point;
@@ -22,7 +22,58 @@ mod iter;
 pub use iter::*;
 mod all_versions_iter;
 pub use all_versions_iter::*;
+mod merging_iter;
+pub use merging_iter::*;
+
+#[cfg(test)]
+mod tests;
+
+/// A single key-value pair destined for [`LogFile::insert_batch`].
+#[derive(Debug, Clone, Copy)]
+pub struct Entry<'a> {
+  meta: Meta,
+  key: &'a [u8],
+  value: &'a [u8],
+}
+
+impl<'a> Entry<'a> {
+  /// Creates a new batch entry.
+  #[inline]
+  pub const fn new(meta: Meta, key: &'a [u8], value: &'a [u8]) -> Self {
+    Self { meta, key, value }
+  }
+
+  /// Returns the entry's metadata.
+  #[inline]
+  pub const fn meta(&self) -> Meta {
+    self.meta
+  }
+
+  /// Returns the entry's key.
+  #[inline]
+  pub const fn key(&self) -> &'a [u8] {
+    self.key
+  }
 
+  /// Returns the entry's value.
+  #[inline]
+  pub const fn value(&self) -> &'a [u8] {
+    self.value
+  }
+}
+
+// NOTE: reserving virtual address space up front and only committing file
+// length as `has_space` fails (parity-db's approach) needs a `SkipMap` that
+// can extend an already-mapped arena in place without relocating it --
+// `create` below hands `SklOpenOptions::new().create_new(Some(opts.size))`
+// straight to `SkipMap::map_mut_with_options_and_comparator_and_path_builder`,
+// which commits exactly `opts.size` up front and has no `grow`/`remap` call
+// this crate could retry into once that fills up. An `ensure_capacity`/
+// `grow` on `LogFile` would have nothing to extend through, and a
+// `remaining_reserved()` would have nothing to report beyond what
+// `has_space` (below) already derives from `self.map.remaining()` -- the
+// hard capacity ceiling this request wants softened is owned by `SkipMap`,
+// not by this wrapper.
 /// A append-only log based on on-disk [`SkipMap`] for key-value databases based on bitcask model.
 pub struct LogFile<C = Ascend> {
   pub(super) map: SkipMap<Meta, Arc<C>>,
@@ -77,6 +128,16 @@ impl<C> LogFile<C> {
   }
 }
 
+impl<C: Comparator> LogFile<C> {
+  /// Returns the comparator this log was constructed with, so a caller
+  /// merging several logs (see [`MergingIter`]) can order keys across them
+  /// consistently with how each log orders its own.
+  #[inline]
+  pub fn comparator(&self) -> &Arc<C> {
+    self.map.comparator()
+  }
+}
+
 impl<C: Comparator> LogFile<C> {
   /// Create a new log with the given options.
   #[cfg(feature = "std")]
@@ -341,26 +402,80 @@ impl<C: Comparator> LogFile<C> {
     }
   }
 
-  // /// Inserts a batch of key-value pairs to the log.
-  // ///
-  // /// ## Warning
-  // /// This method does not guarantee atomicity, which means that if the method fails in the middle of writing the batch,
-  // /// some of the key-value pairs may be written to the log.
-  // #[inline]
-  // pub fn insert_many(&self, batch: &[Entry]) -> Result<(), LogFileError> {
-  //   for (idx, ent) in batch.iter().enumerate() {
-  //     self
-  //       .map
-  //       .insert(ent.meta(), ent.key(), ent.value())
-  //       .map_err(|e| LogFileError::WriteBatch { idx, source: e })?;
-  //   }
-
-  //   if self.sync_on_write {
-  //     self.flush()?;
-  //   }
-
-  //   Ok(())
-  // }
+  /// Inserts a batch of key-value pairs to the log atomically: either every
+  /// (deduplicated) entry ends up linked into the log, or none do.
+  ///
+  /// Entries are first deduplicated LevelDB-write-batch style -- if `batch`
+  /// repeats a key, only the last entry for that key is kept, as if the
+  /// earlier ones had never been appended. Each surviving entry is then
+  /// [`allocate`](Self::allocate)d (or [`allocate_at_height`](Self::allocate_at_height)d,
+  /// for `height`) up front, checking [`has_space`](Self::has_space) before
+  /// each allocation so a failure is reported via [`LogFileError::InsufficientSpace`]
+  /// before anything has been linked. Only once every surviving entry has
+  /// been reserved are the nodes linked; [`flush`](Self::flush) then runs at
+  /// most once, if `sync_on_write` is set.
+  pub fn insert_batch(&self, batch: &[Entry<'_>]) -> Result<(), LogFileError> {
+    self.insert_batch_in(batch, None)
+  }
+
+  /// Like [`insert_batch`](Self::insert_batch), but every surviving entry is
+  /// allocated at the given height.
+  pub fn insert_batch_at_height(
+    &self,
+    batch: &[Entry<'_>],
+    height: skl::u5,
+  ) -> Result<(), LogFileError> {
+    self.insert_batch_in(batch, Some(height))
+  }
+
+  fn insert_batch_in(
+    &self,
+    batch: &[Entry<'_>],
+    height: Option<skl::u5>,
+  ) -> Result<(), LogFileError> {
+    // Last-write-wins dedup: only the last occurrence of each key survives,
+    // in its original position in the batch.
+    let mut last_idx = std::collections::HashMap::with_capacity(batch.len());
+    for (idx, ent) in batch.iter().enumerate() {
+      last_idx.insert(ent.key(), idx);
+    }
+
+    let mut nodes = std::vec::Vec::with_capacity(last_idx.len());
+    for (idx, ent) in batch.iter().enumerate() {
+      if last_idx.get(ent.key()) != Some(&idx) {
+        continue;
+      }
+
+      let height = height.unwrap_or_else(|| self.random_height());
+      if !self.has_space(height, ent.key().len() as u32, ent.value().len() as u32) {
+        return Err(LogFileError::InsufficientSpace {
+          idx,
+          required: SkipMap::<Meta, C>::estimated_node_size(
+            height,
+            ent.key().len() as u32,
+            ent.value().len() as u32,
+          ) as u64,
+          remaining: self.map.remaining() as u64,
+        });
+      }
+
+      let node = self
+        .map
+        .allocate_at_height(ent.meta(), height, ent.key(), ent.value())
+        .map_err(|source| LogFileError::WriteBatch { idx, source })?;
+      nodes.push(node);
+    }
+
+    for node in nodes {
+      self.map.link(node).map_err(LogFileError::Log)?;
+    }
+
+    if self.sync_on_write {
+      self.flush()?;
+    }
+
+    Ok(())
+  }
 
   #[inline]
   pub(crate) fn remove(&self, meta: Meta, key: &[u8]) -> Result<(), LogFileError> {
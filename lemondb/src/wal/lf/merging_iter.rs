@@ -0,0 +1,258 @@
+use super::*;
+
+/// A k-way merging iterator across several [`LogFile`]s of one table,
+/// yielding a single globally-ordered, de-duplicated stream -- the normal
+/// bitcask read path, where a key's latest value lives in whichever log
+/// wrote it last.
+///
+/// Built on each log's [`LogFileAllVersionsIter`] (via
+/// [`LogFile::iter_all_versions`]/[`LogFile::range_all_versions`]) rather
+/// than the tombstone-filtering [`LogFileIter`]: a log that deleted a key
+/// still has to contribute that tombstone to the merge so it can suppress
+/// an older value living in a different log, even though that log's own
+/// plain [`LogFileIter`] would never surface it. Each log's own history of
+/// a key -- older versions beneath the newest one at or below the `version`
+/// ceiling passed to [`Self::new`]/[`Self::range`] -- is skipped internally and never re-surfaces as a
+/// competing candidate once that key has been resolved once.
+///
+/// Selection across logs is a linear scan of the "head" (or, for
+/// [`DoubleEndedIterator`], "tail") entry currently peeked from each
+/// sub-iterator rather than a binary heap: for the handful of frozen logs
+/// a table accumulates before `CompactionPolicy` (in `lemondb-core`) merges
+/// them, the two cost the same in practice, and the
+/// linear scan needs no `Ord` impl for a runtime comparator. When more
+/// than one log's head shares the same key, only the entry with the
+/// largest `Meta` version is surfaced; the rest are dropped, and the
+/// winner itself is dropped too (without being yielded) if it turns out
+/// to be a tombstone.
+///
+/// All logs passed to [`Self::new`]/[`Self::range`] must share the same
+/// comparator -- true by construction, since they all belong to one table
+/// opened with one `cmp`.
+pub struct MergingIter<'a, C, Q: ?Sized = &'static [u8], R = core::ops::RangeFull> {
+  iters: Vec<LogFileAllVersionsIter<'a, C, Q, R>>,
+  heads: Vec<Option<VersionedEntryRef<'a, Meta>>>,
+  tails: Vec<Option<VersionedEntryRef<'a, Meta>>>,
+  // The key each log's head/tail was last resolved against, so an older
+  // version of that same key still buried in that log is skipped instead
+  // of resurfacing as a fresh candidate.
+  resolved_head: Vec<Option<std::vec::Vec<u8>>>,
+  resolved_tail: Vec<Option<std::vec::Vec<u8>>>,
+  cmp: Arc<C>,
+}
+
+impl<'a, C: Comparator> MergingIter<'a, C> {
+  /// Builds a merging iterator over the entries (less than or equal to
+  /// `version`) of `logs`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `logs` is empty -- there is no comparator to merge by.
+  pub fn new(logs: &[&'a LogFile<C>], version: u64) -> Self {
+    let cmp = logs
+      .first()
+      .unwrap_or_else(|| panic!("MergingIter::new requires at least one log"))
+      .comparator()
+      .clone();
+    let iters: Vec<_> = logs.iter().map(|log| log.iter_all_versions(version)).collect();
+    let len = iters.len();
+    Self {
+      iters,
+      heads: (0..len).map(|_| None).collect(),
+      tails: (0..len).map(|_| None).collect(),
+      resolved_head: (0..len).map(|_| None).collect(),
+      resolved_tail: (0..len).map(|_| None).collect(),
+      cmp,
+    }
+  }
+}
+
+impl<'a, C, Q, R> MergingIter<'a, C, Q, R>
+where
+  C: Comparator,
+  &'a [u8]: PartialOrd<Q>,
+  Q: ?Sized + PartialOrd<&'a [u8]>,
+  R: RangeBounds<Q> + Clone,
+{
+  /// Builds a merging iterator over the entries (less than or equal to
+  /// `version`) of `logs` that fall within `range`, the same bounds
+  /// [`LogFile::range`] takes.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `logs` is empty -- there is no comparator to merge by.
+  pub fn range(logs: &[&'a LogFile<C>], version: u64, range: R) -> Self {
+    let cmp = logs
+      .first()
+      .unwrap_or_else(|| panic!("MergingIter::range requires at least one log"))
+      .comparator()
+      .clone();
+    let iters: Vec<_> = logs
+      .iter()
+      .map(|log| log.range_all_versions(version, range.clone()))
+      .collect();
+    let len = iters.len();
+    Self {
+      iters,
+      heads: (0..len).map(|_| None).collect(),
+      tails: (0..len).map(|_| None).collect(),
+      resolved_head: (0..len).map(|_| None).collect(),
+      resolved_tail: (0..len).map(|_| None).collect(),
+      cmp,
+    }
+  }
+}
+
+impl<'a, C, Q, R> Iterator for MergingIter<'a, C, Q, R>
+where
+  C: Comparator,
+  &'a [u8]: PartialOrd<Q>,
+  Q: ?Sized + PartialOrd<&'a [u8]>,
+  R: RangeBounds<Q>,
+{
+  type Item = Result<VersionedEntryRef<'a, Meta>, LogFileError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      for idx in 0..self.iters.len() {
+        if self.heads[idx].is_some() {
+          continue;
+        }
+
+        loop {
+          match self.iters[idx].next() {
+            Some(Ok(ent)) => {
+              if self.resolved_head[idx].as_deref() == Some(ent.key()) {
+                continue;
+              }
+              self.heads[idx] = Some(ent);
+              break;
+            }
+            Some(Err(e)) => return Some(Err(e)),
+            None => break,
+          }
+        }
+      }
+
+      let mut winner = None;
+      for (idx, head) in self.heads.iter().enumerate() {
+        let Some(ent) = head else { continue };
+        winner = Some(match winner {
+          None => idx,
+          Some(best) => {
+            let best_ent = self.heads[best].as_ref().unwrap();
+            match self.cmp.compare(ent.key(), best_ent.key()) {
+              core::cmp::Ordering::Less => idx,
+              core::cmp::Ordering::Equal
+                if ent.trailer().version() > best_ent.trailer().version() =>
+              {
+                idx
+              }
+              _ => best,
+            }
+          }
+        });
+      }
+
+      let Some(winner) = winner else { return None };
+      let winning_key = self.heads[winner].as_ref().unwrap().key().to_vec();
+
+      let mut result = None;
+      for idx in 0..self.heads.len() {
+        if let Some(ent) = &self.heads[idx] {
+          if self.cmp.compare(ent.key(), winning_key.as_slice()) == core::cmp::Ordering::Equal {
+            self.resolved_head[idx] = Some(winning_key.clone());
+            if idx == winner {
+              result = self.heads[idx].take();
+            } else {
+              self.heads[idx] = None;
+            }
+          }
+        }
+      }
+
+      if let Some(ent) = result {
+        if ent.is_removed() {
+          continue;
+        }
+        return Some(Ok(ent));
+      }
+    }
+  }
+}
+
+impl<'a, C, Q, R> DoubleEndedIterator for MergingIter<'a, C, Q, R>
+where
+  C: Comparator,
+  &'a [u8]: PartialOrd<Q>,
+  Q: ?Sized + PartialOrd<&'a [u8]>,
+  R: RangeBounds<Q>,
+{
+  fn next_back(&mut self) -> Option<Self::Item> {
+    loop {
+      for idx in 0..self.iters.len() {
+        if self.tails[idx].is_some() {
+          continue;
+        }
+
+        loop {
+          match self.iters[idx].next_back() {
+            Some(Ok(ent)) => {
+              if self.resolved_tail[idx].as_deref() == Some(ent.key()) {
+                continue;
+              }
+              self.tails[idx] = Some(ent);
+              break;
+            }
+            Some(Err(e)) => return Some(Err(e)),
+            None => break,
+          }
+        }
+      }
+
+      let mut winner = None;
+      for (idx, tail) in self.tails.iter().enumerate() {
+        let Some(ent) = tail else { continue };
+        winner = Some(match winner {
+          None => idx,
+          Some(best) => {
+            let best_ent = self.tails[best].as_ref().unwrap();
+            match self.cmp.compare(ent.key(), best_ent.key()) {
+              core::cmp::Ordering::Greater => idx,
+              core::cmp::Ordering::Equal
+                if ent.trailer().version() > best_ent.trailer().version() =>
+              {
+                idx
+              }
+              _ => best,
+            }
+          }
+        });
+      }
+
+      let Some(winner) = winner else { return None };
+      let winning_key = self.tails[winner].as_ref().unwrap().key().to_vec();
+
+      let mut result = None;
+      for idx in 0..self.tails.len() {
+        if let Some(ent) = &self.tails[idx] {
+          if self.cmp.compare(ent.key(), winning_key.as_slice()) == core::cmp::Ordering::Equal {
+            self.resolved_tail[idx] = Some(winning_key.clone());
+            if idx == winner {
+              result = self.tails[idx].take();
+            } else {
+              self.tails[idx] = None;
+            }
+          }
+        }
+      }
+
+      if let Some(ent) = result {
+        if ent.is_removed() {
+          continue;
+        }
+        return Some(Ok(ent));
+      }
+    }
+  }
+}
@@ -0,0 +1,56 @@
+use super::*;
+use std::sync::Arc;
+
+fn new_in_memory_log(size: u64) -> LogFile<Ascend> {
+  LogFile::<Ascend>::create(
+    Arc::new(Ascend),
+    CreateOptions::new(Fid::new(0))
+      .with_size(size)
+      .with_in_memory(Some(MemoryMode::Memory)),
+  )
+  .unwrap()
+}
+
+#[test]
+fn insert_batch_dedups_last_write_wins() {
+  let log = new_in_memory_log(4096);
+
+  let entries = [
+    Entry::new(Meta::new(1), b"k", b"first"),
+    Entry::new(Meta::new(2), b"k", b"second"),
+  ];
+  log.insert_batch(&entries).unwrap();
+
+  let mut seen = 0;
+  for ent in log.iter(2) {
+    let ent = ent.unwrap();
+    assert_eq!(ent.key(), b"k");
+    assert_eq!(ent.value(), b"second");
+    seen += 1;
+  }
+  assert_eq!(
+    seen, 1,
+    "last-write-wins dedup should link exactly one node per repeated key"
+  );
+}
+
+#[test]
+fn insert_batch_rolls_back_on_insufficient_space() {
+  // Sized to fit the first entry's reservation but not the second's, so the
+  // reservation phase fails partway through the batch.
+  let log = new_in_memory_log(64);
+
+  let big_value = std::vec![0u8; 4096];
+  let entries = [
+    Entry::new(Meta::new(1), b"a", b"small"),
+    Entry::new(Meta::new(2), b"b", &big_value),
+  ];
+
+  let err = log.insert_batch(&entries).unwrap_err();
+  assert!(matches!(err, LogFileError::InsufficientSpace { .. }));
+
+  assert!(
+    log.iter(2).next().is_none(),
+    "a batch that fails its reservation phase must not leave any entry linked into the log"
+  );
+}
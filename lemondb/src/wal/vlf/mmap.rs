@@ -30,6 +30,10 @@ pub struct MmapValueLog {
   len: u64,
   cap: u64,
   ro: bool,
+  /// `len` as of the last incremental sync issued by [`Self::sync_if_needed`],
+  /// i.e. the end of the region already flushed to disk. The gap between this
+  /// and `len` is the dirty, un-synced tail.
+  synced_len: u64,
 }
 
 impl MmapValueLog {
@@ -62,6 +66,7 @@ impl MmapValueLog {
       len: 0,
       cap: opts.size,
       ro: false,
+      synced_len: 0,
     })
   }
 
@@ -88,6 +93,8 @@ impl MmapValueLog {
       len: cap,
       cap,
       ro: true,
+      // An opened (read-only) log is by definition already fully on disk.
+      synced_len: cap,
     })
   }
 
@@ -137,6 +144,47 @@ impl MmapValueLog {
     }
   }
 
+  // NOTE: nothing calls this yet. The request this was added for
+  // (incremental fsync bounded by a `bytes_per_sync` option, rather than only
+  // syncing on close/rewind) needs that threshold to come from somewhere --
+  // `WalOptions`/`CreateOptions` is the natural home, matching how
+  // `value_threshold` already gates inline-vs-pointer storage in
+  // `Wal::insert_entry_to_vlog` -- but neither type exists in this tree yet
+  // (see the compression/encryption notes above `insert_entry_to_vlog` in
+  // `wal.rs` for the same root gap). The mechanism below is self-contained
+  // and ready to wire in once that option exists: callers would invoke it
+  // with their configured threshold right after each `write`.
+  /// Flushes the mapping's dirty `[synced_len, len)` range to disk with
+  /// `MmapMut::flush_async_range` if at least `bytes_per_sync` bytes have
+  /// accumulated since the last sync, then advances `synced_len` to `len`.
+  /// A `bytes_per_sync` of `0` disables incremental sync: the call returns
+  /// without flushing anything, the same as never calling this.
+  ///
+  /// Only ever flushes up to `len`, never past it: a value log that is later
+  /// [`Self::rewind`]-ed because a batch failed only ever had bytes in
+  /// `[synced_len, len)` synced in the first place, so rewinding first and
+  /// then re-writing can't leave a previously-synced-and-since-discarded
+  /// record for recovery to replay -- there's nothing synced past the new,
+  /// shorter `len` to begin with.
+  pub fn sync_if_needed(&mut self, bytes_per_sync: u64) -> Result<(), ValueLogError> {
+    if bytes_per_sync == 0 || self.len <= self.synced_len {
+      return Ok(());
+    }
+
+    if self.len - self.synced_len < bytes_per_sync {
+      return Ok(());
+    }
+
+    if let Memmap::MapMut { ref mmap, .. } = self.buf {
+      let start = self.synced_len as usize;
+      let end = self.len as usize;
+      mmap.flush_async_range(start, end - start)?;
+    }
+
+    self.synced_len = self.len;
+    Ok(())
+  }
+
   /// Returns error if the pointer is invalid
   #[inline]
   pub fn check_pointer(&self, pointer: Pointer) -> Result<(), ValueLogError> {
@@ -171,6 +219,25 @@ impl MmapValueLog {
     })
   }
 
+  /// Like [`Self::read`], but copies the header, key and value bytes into
+  /// `buf` (cleared and resized first) instead of borrowing from the
+  /// mapping, so a caller reading the same pointer repeatedly -- e.g.
+  /// [`EntryRef::value_into`](crate::wal::EntryRef::value_into) iterating
+  /// many pointer-backed entries -- reuses one buffer's allocation instead
+  /// of paying for a fresh one (or, for a buffered/compressed/encrypted
+  /// value log, a decode) on every access.
+  #[inline]
+  pub(crate) fn read_into(
+    &self,
+    offset: usize,
+    size: usize,
+    buf: &mut std::vec::Vec<u8>,
+  ) -> Result<(), ValueLogError> {
+    buf.clear();
+    buf.extend_from_slice(self.read(offset, size)?);
+    Ok(())
+  }
+
   /// Returns a byte slice which contains header, key and value.
   ///
   /// # Safety
@@ -192,6 +259,11 @@ impl MmapValueLog {
     }
 
     self.len = self.len.saturating_sub(size as u64);
+    // `synced_len` must never claim more than `len` is synced, or a
+    // subsequent `sync_if_needed` would think bytes past the new, shorter
+    // `len` are already on disk and skip flushing them once they're
+    // rewritten.
+    self.synced_len = self.synced_len.min(self.len);
     Ok(())
   }
 
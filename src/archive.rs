@@ -0,0 +1,620 @@
+//! Portable single-file export/import of a whole database's manifest and
+//! segments, the way an `hpk` package bundles everything a package needs
+//! into one movable artifact.
+//!
+//! An archive is laid out as:
+//!
+//! ```text
+//! +-------+---------+--------------+----------+----------------------+
+//! | magic | version | manifest len | manifest | segment count | ... |
+//! +-------+---------+--------------+----------+----------------------+
+//! ```
+//!
+//! followed by that many segments, each a fixed header (`Fid` as a `u64`,
+//! kind byte, codec byte, `u64` length, `u32` checksum) and then its bytes. The manifest section records every table this archive knows
+//! about and the fids of the log/value-log segments that belong to it (see
+//! [`ArchivedManifest::from_manifest`]); segments are the raw bytes of those
+//! files, each carrying its own `Fid`, kind, codec and a `crc32fast`
+//! checksum so [`import`] can catch a truncated or bit-rotted segment
+//! before admitting it.
+//!
+//! This module only knows how to frame and checksum bytes the caller hands
+//! it -- it does not itself walk a [`Db`](crate::Db)'s tables collecting
+//! segment bytes. Every [`ValueLog`](crate::wal::vlf::ValueLog) already
+//! supports exactly the read this needs (`read(0, len())` returns its
+//! entire contents as a byte slice), so wiring up value-log segments is a
+//! small addition to `Db`/`Table`. `LogFile`'s active/frozen key logs,
+//! though, are backed by a `crossbeam_skiplist`/`skl` arena with no
+//! byte-level accessor -- there is no `LogFile::as_bytes` to hand this
+//! module, so exporting key-log segments needs that accessor added first
+//! (a separate, `LogFile`-focused change, not something an archive format
+//! should paper over by inventing its own serialization of the arena).
+
+use std::io::{self, Read, Write};
+use std::vec::Vec;
+
+use smol_str::SmolStr;
+
+use crate::{
+  manifest::{Manifest, ManifestRecord},
+  options::CompressionType,
+  types::{DecodeError, Fid, TableId},
+  util::VarintError,
+};
+
+/// Magic bytes identifying an archive produced by [`export`].
+const MAGIC: [u8; 8] = *b"LMDBARCH";
+
+/// The archive format version [`export`] writes and [`import`] expects.
+/// Bumped whenever the manifest or segment section's layout changes in a
+/// way an older reader couldn't parse.
+const FORMAT_VERSION: u16 = 1;
+
+/// A ceiling on how many segments [`import`] will reserve `Vec` capacity for
+/// up front, independent of what the stream's segment count claims. Unlike
+/// the manifest's table/log counts (see [`capped_capacity`]), the segment
+/// count is read off a `Read` stream rather than an in-memory buffer, so
+/// there's no "remaining bytes" to bound it against -- a flat ceiling plays
+/// the same role `read_exact_len` plays for segment bytes themselves: the
+/// claimed count can't force an allocation bigger than this before the loop
+/// that actually reads segments gets a chance to fail on a truncated stream.
+const MAX_PREALLOC_SEGMENTS: usize = 4096;
+
+/// A decoded count (table count, log count, ...) is read straight out of
+/// `buf` before anything it describes has been validated to exist, so a
+/// corrupt or hand-crafted manifest can claim billions of entries to force
+/// a multi-gigabyte `Vec::with_capacity` before the loop that actually
+/// decodes them gets a chance to run out of bytes and fail. Bounding the
+/// reservation by how many `min_item_size`-sized entries the bytes actually
+/// left in `buf` could possibly hold makes the upfront allocation track
+/// reality; the decode loop still returns [`ArchiveError::UnexpectedEof`] if
+/// the claimed count turns out to be a lie.
+#[inline]
+fn capped_capacity(claimed: usize, remaining: usize, min_item_size: usize) -> usize {
+  claimed.min(remaining / min_item_size.max(1))
+}
+
+/// Which kind of file a [`Segment`] holds the bytes of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub(crate) enum SegmentKind {
+  /// An active/frozen key log (`.wal`) segment.
+  Log = 0,
+  /// A value log (`.vlog`) segment.
+  ValueLog = 1,
+}
+
+impl SegmentKind {
+  #[inline]
+  const fn from_u8(v: u8) -> Result<Self, ArchiveError> {
+    match v {
+      0 => Ok(Self::Log),
+      1 => Ok(Self::ValueLog),
+      other => Err(ArchiveError::UnknownSegmentKind(other)),
+    }
+  }
+}
+
+/// A segment to write with [`export`]: the file id and kind it was read
+/// from, the codec its bytes are already compressed with (or
+/// [`CompressionType::None`]), and the raw on-disk bytes themselves.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Segment<'a> {
+  fid: Fid,
+  kind: SegmentKind,
+  codec: CompressionType,
+  bytes: &'a [u8],
+}
+
+impl<'a> Segment<'a> {
+  /// Creates a new segment to be written by [`export`].
+  #[inline]
+  pub(crate) const fn new(fid: Fid, kind: SegmentKind, codec: CompressionType, bytes: &'a [u8]) -> Self {
+    Self {
+      fid,
+      kind,
+      codec,
+      bytes,
+    }
+  }
+}
+
+/// A segment read back by [`import`]: the same shape as [`Segment`], but
+/// owning its bytes since they were just read off the wire.
+#[derive(Debug, Clone)]
+pub(crate) struct ImportedSegment {
+  fid: Fid,
+  kind: SegmentKind,
+  codec: CompressionType,
+  bytes: Vec<u8>,
+}
+
+impl ImportedSegment {
+  /// Returns the file id this segment was exported from.
+  #[inline]
+  pub(crate) const fn fid(&self) -> Fid {
+    self.fid
+  }
+
+  /// Returns which kind of file this segment holds the bytes of.
+  #[inline]
+  pub(crate) const fn kind(&self) -> SegmentKind {
+    self.kind
+  }
+
+  /// Returns the codec `bytes` is compressed with, or
+  /// [`CompressionType::None`] if stored verbatim.
+  #[inline]
+  pub(crate) const fn codec(&self) -> CompressionType {
+    self.codec
+  }
+
+  /// Returns the segment's raw bytes, already checksum-verified by
+  /// [`import`].
+  #[inline]
+  pub(crate) fn bytes(&self) -> &[u8] {
+    &self.bytes
+  }
+}
+
+/// A log or value-log fid recorded against a table in the manifest section,
+/// together with the codec it was written with.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ArchivedLog {
+  fid: Fid,
+  codec: CompressionType,
+}
+
+impl ArchivedLog {
+  /// Returns the file id.
+  #[inline]
+  pub(crate) const fn fid(&self) -> Fid {
+    self.fid
+  }
+
+  /// Returns the compression codec this log was written with.
+  #[inline]
+  pub(crate) const fn codec(&self) -> CompressionType {
+    self.codec
+  }
+}
+
+/// A single table's entry in the manifest section: its id, name, and the
+/// fids of its key logs and value logs.
+#[derive(Debug, Clone)]
+pub(crate) struct ArchivedTable {
+  id: TableId,
+  name: SmolStr,
+  logs: Vec<ArchivedLog>,
+  vlogs: Vec<ArchivedLog>,
+}
+
+impl ArchivedTable {
+  /// Returns the table's id.
+  #[inline]
+  pub(crate) const fn id(&self) -> TableId {
+    self.id
+  }
+
+  /// Returns the table's name.
+  #[inline]
+  pub(crate) fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Returns the table's key-log fids and their codecs.
+  #[inline]
+  pub(crate) fn logs(&self) -> &[ArchivedLog] {
+    &self.logs
+  }
+
+  /// Returns the table's value-log fids and their codecs.
+  #[inline]
+  pub(crate) fn value_logs(&self) -> &[ArchivedLog] {
+    &self.vlogs
+  }
+}
+
+/// The manifest section of an archive: every live table [`export`] saw,
+/// plus the high-water marks a restore needs to carry forward.
+#[derive(Debug, Clone)]
+pub(crate) struct ArchivedManifest {
+  last_fid: Fid,
+  last_table_id: TableId,
+  sequence: u64,
+  tables: Vec<ArchivedTable>,
+}
+
+impl ArchivedManifest {
+  /// Builds the manifest section from the live (non-removed) tables a
+  /// [`Manifest`] currently tracks, via [`Manifest::tables`], filtering out
+  /// any [`TableManifest::is_removed`](crate::manifest::TableManifest) entry
+  /// the same way a manifest rewrite would.
+  pub(crate) fn from_manifest(manifest: &Manifest) -> Self {
+    let (last_fid, last_table_id, sequence) = match manifest.metadata_record() {
+      ManifestRecord::Metadata {
+        next_fid,
+        next_table_id,
+        sequence,
+      } => (next_fid, next_table_id, sequence),
+      _ => unreachable!("Manifest::metadata_record always returns a Metadata record"),
+    };
+
+    let tables = manifest
+      .tables()
+      .filter(|table| !table.is_removed())
+      .map(|table| ArchivedTable {
+        id: table.id,
+        name: table.name.clone(),
+        logs: table
+          .frozen_logs()
+          .map(|fid| ArchivedLog {
+            fid,
+            codec: table.codec(fid),
+          })
+          .collect(),
+        vlogs: table
+          .value_logs()
+          .map(|fid| ArchivedLog {
+            fid,
+            codec: table.codec(fid),
+          })
+          .collect(),
+      })
+      .collect();
+
+    Self {
+      last_fid,
+      last_table_id,
+      sequence,
+      tables,
+    }
+  }
+
+  /// Returns the next-fid high-water mark to restore
+  /// [`AtomicFid`](crate::types::AtomicFid) from.
+  #[inline]
+  pub(crate) const fn last_fid(&self) -> Fid {
+    self.last_fid
+  }
+
+  /// Returns the next-table-id high-water mark to restore
+  /// [`AtomicTableId`](crate::types::AtomicTableId) from.
+  #[inline]
+  pub(crate) const fn last_table_id(&self) -> TableId {
+    self.last_table_id
+  }
+
+  /// Returns the latest committed sequence number at export time.
+  #[inline]
+  pub(crate) const fn sequence(&self) -> u64 {
+    self.sequence
+  }
+
+  /// Returns the archived tables.
+  #[inline]
+  pub(crate) fn tables(&self) -> &[ArchivedTable] {
+    &self.tables
+  }
+
+  /// Returns the largest fid referenced anywhere in this manifest section
+  /// (the high-water mark itself, or any table's log/vlog fid, whichever
+  /// is larger), for [`import`] callers reseeding `AtomicFid` against
+  /// whatever actually landed on disk rather than trusting the recorded
+  /// high-water mark alone.
+  pub(crate) fn max_fid(&self) -> Fid {
+    self.tables.iter().fold(self.last_fid, |max, table| {
+      let table_max = table
+        .logs
+        .iter()
+        .chain(table.vlogs.iter())
+        .map(ArchivedLog::fid)
+        .fold(max, Fid::max);
+      max.max(table_max)
+    })
+  }
+
+  fn encode(&self, out: &mut Vec<u8>) -> Result<(), ArchiveError> {
+    let mut buf = [0u8; 10];
+
+    let n = self.last_fid.encode(&mut buf)?;
+    out.extend_from_slice(&buf[..n]);
+    let n = self.last_table_id.encode(&mut buf)?;
+    out.extend_from_slice(&buf[..n]);
+    out.extend_from_slice(&self.sequence.to_le_bytes());
+    out.extend_from_slice(&(self.tables.len() as u32).to_le_bytes());
+
+    for table in &self.tables {
+      let n = table.id.encode(&mut buf)?;
+      out.extend_from_slice(&buf[..n]);
+
+      let name = table.name.as_bytes();
+      out.push(name.len() as u8);
+      out.extend_from_slice(name);
+
+      Self::encode_logs(&table.logs, out, &mut buf)?;
+      Self::encode_logs(&table.vlogs, out, &mut buf)?;
+    }
+
+    Ok(())
+  }
+
+  fn encode_logs(
+    logs: &[ArchivedLog],
+    out: &mut Vec<u8>,
+    buf: &mut [u8; 10],
+  ) -> Result<(), ArchiveError> {
+    out.extend_from_slice(&(logs.len() as u32).to_le_bytes());
+    for log in logs {
+      let n = log.fid.encode(buf)?;
+      out.extend_from_slice(&buf[..n]);
+      out.push(log.codec as u8);
+    }
+    Ok(())
+  }
+
+  fn decode(buf: &[u8]) -> Result<Self, ArchiveError> {
+    let mut cur = 0;
+
+    let (n, last_fid) = Fid::decode(&buf[cur..])?;
+    cur += n;
+    let (n, last_table_id) = TableId::decode(&buf[cur..])?;
+    cur += n;
+
+    let sequence = read_u64(buf, &mut cur)?;
+    let table_count = read_u32(buf, &mut cur)? as usize;
+
+    // Minimum bytes a table record can possibly take: a 1-byte `TableId`
+    // varint, a 1-byte name length, and two 4-byte (empty) log/vlog counts.
+    let mut tables = Vec::with_capacity(capped_capacity(table_count, buf.len() - cur, 10));
+    for _ in 0..table_count {
+      let (n, id) = TableId::decode(&buf[cur..])?;
+      cur += n;
+
+      let name_len = *buf.get(cur).ok_or(ArchiveError::UnexpectedEof)? as usize;
+      cur += 1;
+      let name_bytes = buf
+        .get(cur..cur + name_len)
+        .ok_or(ArchiveError::UnexpectedEof)?;
+      let name = SmolStr::from(String::from_utf8_lossy(name_bytes));
+      cur += name_len;
+
+      let logs = Self::decode_logs(buf, &mut cur)?;
+      let vlogs = Self::decode_logs(buf, &mut cur)?;
+
+      tables.push(ArchivedTable {
+        id,
+        name,
+        logs,
+        vlogs,
+      });
+    }
+
+    Ok(Self {
+      last_fid,
+      last_table_id,
+      sequence,
+      tables,
+    })
+  }
+
+  fn decode_logs(buf: &[u8], cur: &mut usize) -> Result<Vec<ArchivedLog>, ArchiveError> {
+    let count = read_u32(buf, cur)? as usize;
+    // Minimum bytes a log entry can possibly take: a 1-byte `Fid` varint and
+    // a 1-byte codec.
+    let mut logs = Vec::with_capacity(capped_capacity(count, buf.len() - *cur, 2));
+    for _ in 0..count {
+      let (n, fid) = Fid::decode(&buf[*cur..])?;
+      *cur += n;
+      let codec_byte = *buf.get(*cur).ok_or(ArchiveError::UnexpectedEof)?;
+      *cur += 1;
+      logs.push(ArchivedLog {
+        fid,
+        codec: CompressionType::from_u8(codec_byte),
+      });
+    }
+    Ok(logs)
+  }
+}
+
+#[inline]
+fn read_u32(buf: &[u8], cur: &mut usize) -> Result<u32, ArchiveError> {
+  let bytes = buf
+    .get(*cur..*cur + 4)
+    .ok_or(ArchiveError::UnexpectedEof)?;
+  *cur += 4;
+  Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+#[inline]
+fn read_u64(buf: &[u8], cur: &mut usize) -> Result<u64, ArchiveError> {
+  let bytes = buf
+    .get(*cur..*cur + 8)
+    .ok_or(ArchiveError::UnexpectedEof)?;
+  *cur += 8;
+  Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Errors returned by [`export`]/[`import`].
+#[derive(Debug)]
+pub(crate) enum ArchiveError {
+  /// An I/O error occurred reading from or writing to the archive stream.
+  Io(io::Error),
+  /// The stream did not start with the expected [`MAGIC`] bytes, i.e. it
+  /// isn't an archive produced by [`export`] at all.
+  InvalidMagic,
+  /// The archive declares a format version newer than this build of
+  /// lemondb knows how to read.
+  UnsupportedVersion(u16),
+  /// The stream ended before a length-prefixed section finished decoding.
+  UnexpectedEof,
+  /// A segment's kind byte wasn't a recognized [`SegmentKind`].
+  UnknownSegmentKind(u8),
+  /// A segment's bytes didn't match the checksum recorded alongside it.
+  Corrupted {
+    /// The fid of the corrupted segment.
+    fid: Fid,
+  },
+  /// A decoded id overflowed the range its type can represent.
+  Decode(DecodeError),
+  /// Decoding a varint in the manifest section failed.
+  Varint(VarintError),
+}
+
+impl From<io::Error> for ArchiveError {
+  #[inline]
+  fn from(e: io::Error) -> Self {
+    Self::Io(e)
+  }
+}
+
+impl From<DecodeError> for ArchiveError {
+  #[inline]
+  fn from(e: DecodeError) -> Self {
+    Self::Decode(e)
+  }
+}
+
+impl From<VarintError> for ArchiveError {
+  #[inline]
+  fn from(e: VarintError) -> Self {
+    Self::Varint(e)
+  }
+}
+
+impl core::fmt::Display for ArchiveError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Io(e) => write!(f, "{e}"),
+      Self::InvalidMagic => write!(f, "not a lemondb archive"),
+      Self::UnsupportedVersion(v) => write!(f, "unsupported archive format version {v}"),
+      Self::UnexpectedEof => write!(f, "archive ended unexpectedly"),
+      Self::UnknownSegmentKind(b) => write!(f, "unknown segment kind byte {b}"),
+      Self::Corrupted { fid } => write!(f, "segment {fid} failed checksum verification"),
+      Self::Decode(e) => write!(f, "{e}"),
+      Self::Varint(e) => write!(f, "{e}"),
+    }
+  }
+}
+
+impl std::error::Error for ArchiveError {}
+
+/// Writes `manifest` and `segments` to `writer` as a single self-describing
+/// archive (see the module docs for the exact layout).
+pub(crate) fn export<W: Write>(
+  writer: &mut W,
+  manifest: &Manifest,
+  segments: &[Segment<'_>],
+) -> Result<(), ArchiveError> {
+  writer.write_all(&MAGIC)?;
+  writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+
+  let mut manifest_buf = Vec::new();
+  ArchivedManifest::from_manifest(manifest).encode(&mut manifest_buf)?;
+  writer.write_all(&(manifest_buf.len() as u32).to_le_bytes())?;
+  writer.write_all(&manifest_buf)?;
+
+  writer.write_all(&(segments.len() as u32).to_le_bytes())?;
+  for segment in segments {
+    writer.write_all(&segment.fid.as_u64().to_le_bytes())?;
+    writer.write_all(&[segment.kind as u8])?;
+    writer.write_all(&[segment.codec as u8])?;
+    writer.write_all(&(segment.bytes.len() as u64).to_le_bytes())?;
+    writer.write_all(&crc32fast::hash(segment.bytes).to_le_bytes())?;
+    writer.write_all(segment.bytes)?;
+  }
+
+  Ok(())
+}
+
+/// Reads an archive written by [`export`] back from `reader`, verifying
+/// every segment's checksum before returning it. A mismatch is reported as
+/// [`ArchiveError::Corrupted`] rather than silently admitting a bit-rotted
+/// or truncated segment.
+pub(crate) fn import<R: Read>(reader: &mut R) -> Result<(ArchivedManifest, Vec<ImportedSegment>), ArchiveError> {
+  let mut magic = [0u8; 8];
+  reader.read_exact(&mut magic)?;
+  if magic != MAGIC {
+    return Err(ArchiveError::InvalidMagic);
+  }
+
+  let version = read_u16(reader)?;
+  if version > FORMAT_VERSION {
+    return Err(ArchiveError::UnsupportedVersion(version));
+  }
+
+  let manifest_len = read_u32_stream(reader)? as usize;
+  let manifest_buf = read_exact_len(reader, manifest_len)?;
+  let manifest = ArchivedManifest::decode(&manifest_buf)?;
+
+  let segment_count = read_u32_stream(reader)?;
+  let mut segments = Vec::with_capacity((segment_count as usize).min(MAX_PREALLOC_SEGMENTS));
+  for _ in 0..segment_count {
+    let mut fid_buf = [0u8; 8];
+    reader.read_exact(&mut fid_buf)?;
+    let fid = Fid::new(u64::from_le_bytes(fid_buf));
+
+    let mut kind_buf = [0u8; 1];
+    reader.read_exact(&mut kind_buf)?;
+    let kind = SegmentKind::from_u8(kind_buf[0])?;
+
+    let mut codec_buf = [0u8; 1];
+    reader.read_exact(&mut codec_buf)?;
+    let codec = CompressionType::from_u8(codec_buf[0]);
+
+    let mut size_buf = [0u8; 8];
+    reader.read_exact(&mut size_buf)?;
+    let size = u64::from_le_bytes(size_buf) as usize;
+
+    let mut checksum_buf = [0u8; 4];
+    reader.read_exact(&mut checksum_buf)?;
+    let want_checksum = u32::from_le_bytes(checksum_buf);
+
+    let bytes = read_exact_len(reader, size)?;
+    if crc32fast::hash(&bytes) != want_checksum {
+      return Err(ArchiveError::Corrupted { fid });
+    }
+
+    segments.push(ImportedSegment {
+      fid,
+      kind,
+      codec,
+      bytes,
+    });
+  }
+
+  Ok((manifest, segments))
+}
+
+#[inline]
+fn read_u16<R: Read>(reader: &mut R) -> Result<u16, ArchiveError> {
+  let mut buf = [0u8; 2];
+  reader.read_exact(&mut buf)?;
+  Ok(u16::from_le_bytes(buf))
+}
+
+#[inline]
+fn read_u32_stream<R: Read>(reader: &mut R) -> Result<u32, ArchiveError> {
+  let mut buf = [0u8; 4];
+  reader.read_exact(&mut buf)?;
+  Ok(u32::from_le_bytes(buf))
+}
+
+/// Reads exactly `len` bytes from `reader`, the way [`import`] reads every
+/// length-prefixed section. `len` comes straight off the archive stream --
+/// truncated or corrupt input can claim any length it likes -- so this
+/// goes through [`Read::take`] and [`Read::read_to_end`] rather than
+/// `vec![0u8; len]` + [`Read::read_exact`]: the `Vec` only ever grows to
+/// however many bytes the reader actually produced, instead of allocating
+/// `len` bytes upfront before anything has been validated. A stream that
+/// runs out before `len` bytes arrive is reported as
+/// [`ArchiveError::UnexpectedEof`] rather than an I/O error from a short
+/// `read_exact`.
+fn read_exact_len<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, ArchiveError> {
+  let mut bytes = Vec::new();
+  reader.take(len as u64).read_to_end(&mut bytes)?;
+  if bytes.len() != len {
+    return Err(ArchiveError::UnexpectedEof);
+  }
+  Ok(bytes)
+}
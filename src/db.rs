@@ -2,6 +2,33 @@ use std::sync::Arc;
 
 pub use skl::{Ascend, Comparator, Descend};
 
+/// An ASCII case-insensitive [`Comparator`]: orders keys as if every ASCII
+/// letter were lowercased first, falling back to the raw byte order to break
+/// ties between keys that only differ by ASCII case.
+///
+/// [`Db`](sync::Db) and [`Table`](sync::Table) take a comparator as their
+/// `C` parameter, and every write and lookup of a table goes through the
+/// same `Wal<C>`. Their constructors ([`Db::open`](sync::Db::open),
+/// [`Db::open_inmemory`](sync::Db::open_inmemory) and the table-opening
+/// methods), however, build only `Db<Ascend>`/`Table<Ascend>`, so no
+/// database or table is opened with this comparator. A [`Comparator`] only
+/// changes how distinct user keys sort relative to each other -- version
+/// ordering between revisions of the same key is carried entirely by the
+/// [`Meta`](crate::types::Meta) trailer, so it is unaffected by which
+/// collation is chosen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AsciiCaseInsensitive;
+
+impl Comparator for AsciiCaseInsensitive {
+  #[inline]
+  fn compare(&self, a: &[u8], b: &[u8]) -> core::cmp::Ordering {
+    a.iter()
+      .map(u8::to_ascii_lowercase)
+      .cmp(b.iter().map(u8::to_ascii_lowercase))
+      .then_with(|| a.cmp(b))
+  }
+}
+
 /// Synchronous database.
 #[cfg(feature = "sync")]
 pub mod sync;
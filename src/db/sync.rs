@@ -3,13 +3,14 @@ use super::*;
 use crate::{
   error::Error,
   manifest::{ManifestFile, ManifestRecord},
-  options::{MemoryMode, Options, TableOptions, WalOptions},
-  wal::Wal,
-  AtomicFid, Meta, Mu, TableId,
+  options::{ManifestOptions, MemoryMode, Options, TableOptions, WalOptions},
+  wal::{Wal, WalStats},
+  AtomicFid, AtomicSeq, Meta, Mu, TableId,
 };
 
 use core::{
   cell::UnsafeCell,
+  ops::{Bound, RangeBounds},
   sync::atomic::{AtomicBool, Ordering},
 };
 #[cfg(not(feature = "parking_lot"))]
@@ -17,6 +18,7 @@ use std::sync::Mutex;
 
 #[cfg(feature = "std")]
 use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use bytes::Bytes;
 use crossbeam_channel::{Receiver, Sender};
@@ -29,11 +31,56 @@ use aol::CustomFlags;
 use parking_lot::Mutex;
 use smol_str::SmolStr;
 
+/// Shared close/removal state for a single table, consulted by both the
+/// foreground [`Table`] handle and its [`StandaloneTableWriter`] so there is
+/// exactly one authority deciding whether the table may still touch the
+/// manifest.
+///
+/// Modeled on HoraeDB's serial-executor validity flag: once `closed`/
+/// `removed` is observed here, every operation against the table --
+/// including the manifest-appending side effects of a write, such as log
+/// rotation or a new value log segment -- is rejected with
+/// [`Error::TableClosed`]/[`Error::TableRemoved`] before ever reaching
+/// [`ManifestFile::append`](crate::manifest::ManifestFile), rather than only
+/// gating the write itself.
+///
+/// The writer's drain on close or database shutdown does not consult this
+/// flag when `ignore_writes_after_close` is unset: every event already
+/// queued is still applied to the WAL, and such a write can still append to
+/// the manifest (for example on log rotation).
+struct TableValidity {
+  closed: AtomicBool,
+  removed: AtomicBool,
+}
+
+impl TableValidity {
+  const fn new() -> Self {
+    Self {
+      closed: AtomicBool::new(false),
+      removed: AtomicBool::new(false),
+    }
+  }
+
+  fn check(&self, name: &SmolStr) -> Result<(), Error> {
+    if self.closed.load(Ordering::Acquire) {
+      return Err(Error::TableClosed(name.clone()));
+    }
+
+    if self.removed.load(Ordering::Acquire) {
+      return Err(Error::TableRemoved(name.clone()));
+    }
+
+    Ok(())
+  }
+}
+
 struct StandaloneTableWriter<C = Ascend> {
   name: SmolStr,
   id: TableId,
   rx: Receiver<Event>,
   wal: Arc<UnsafeCell<Wal<C>>>,
+  seq: Arc<AtomicSeq>,
+  validity: Arc<TableValidity>,
   ignore_writes_after_close: bool,
   remove_table_rx: Receiver<()>,
   close_table_rx: Receiver<()>,
@@ -47,6 +94,8 @@ impl<C: Comparator + Send + Sync + 'static> StandaloneTableWriter<C> {
     id: TableId,
     rx: Receiver<Event>,
     wal: Arc<UnsafeCell<Wal<C>>>,
+    seq: Arc<AtomicSeq>,
+    validity: Arc<TableValidity>,
     ignore_writes_after_close: bool,
     remove_table_rx: Receiver<()>,
     close_table_rx: Receiver<()>,
@@ -57,6 +106,8 @@ impl<C: Comparator + Send + Sync + 'static> StandaloneTableWriter<C> {
       id,
       rx,
       wal,
+      seq,
+      validity,
       ignore_writes_after_close,
       shutdown_db_rx,
       remove_table_rx,
@@ -104,13 +155,21 @@ impl<C: Comparator + Send + Sync + 'static> StandaloneTableWriter<C> {
               // Safety: we are the only thread that writes the wal.
               let wal = unsafe { &mut *$this.wal.get() };
 
-              if let Err(_e) = tx.send(wal.insert($this.id, 0, &key, &value)) {
+              if let Err(_e) = tx.send(wal.insert($this.id, $this.seq.increment(), &key, &value)) {
                 #[cfg(feature = "tracing")]
                 tracing::error!(table_id=%$this.id, table_name=%$this.name, err=%_e, "failed to send write result");
               }
             }
             Event::WriteBatch { tx, table_id, batch } => {
               assert_eq!($this.id, table_id, "table({})'s writer receive a write event of table({table_id}), please report this bug to https://github.com/al8n/lemondb/issues", $this.id);
+
+              // Safety: we are the only thread that writes the wal.
+              let wal = unsafe { &mut *$this.wal.get() };
+
+              if let Err(_e) = tx.send(wal.insert_batch($this.id, $this.seq.increment(), &batch)) {
+                #[cfg(feature = "tracing")]
+                tracing::error!(table_id=%$this.id, table_name=%$this.name, err=%_e, "failed to send write result");
+              }
             }
             Event::Remove { tx, table_id, key } => {
               assert_eq!($this.id, table_id, "table({})'s writer receive a write event of table({table_id}), please report this bug to https://github.com/al8n/lemondb/issues", $this.id);
@@ -118,7 +177,7 @@ impl<C: Comparator + Send + Sync + 'static> StandaloneTableWriter<C> {
               // Safety: we are the only thread that writes the wal.
               let wal = unsafe { &mut *$this.wal.get() };
 
-              if let Err(_e) = tx.send(wal.remove($this.id, 0, &key)) {
+              if let Err(_e) = tx.send(wal.remove($this.id, $this.seq.increment(), &key)) {
                 #[cfg(feature = "tracing")]
                 tracing::error!(table_id=%$this.id, table_name=%$this.name, err=%_e, "failed to send remove result");
               }
@@ -149,24 +208,56 @@ impl<C: Comparator + Send + Sync + 'static> StandaloneTableWriter<C> {
             Ok(Event::Write { key, value, tx, table_id }) => {
               assert_eq!(id, table_id, "table({id})'s writer receive a write event of table({table_id}), please report this bug to https://github.com/al8n/lemondb/issues");
 
+              if let Err(e) = self.validity.check(&self.name) {
+                if let Err(_e) = tx.send(Err(e)) {
+                  #[cfg(feature = "tracing")]
+                  tracing::error!(table_id=%id, table_name=%self.name, err=%_e, "failed to send write result");
+                }
+                continue;
+              }
+
               // Safety: we are the only thread that writes the wal.
               let wal = unsafe { &mut *self.wal.get() };
 
-              if let Err(_e) = tx.send(wal.insert(id, 0, &key, &value)) {
+              if let Err(_e) = tx.send(wal.insert(id, self.seq.increment(), &key, &value)) {
                 #[cfg(feature = "tracing")]
                 tracing::error!(table_id=%id, table_name=%self.name, err=%_e, "failed to send write result");
               }
             }
             Ok(Event::WriteBatch { table_id, batch, tx }) => {
               assert_eq!(id, table_id, "table({id})'s writer receive a write event of table({table_id}), please report this bug to https://github.com/al8n/lemondb/issues");
+
+              if let Err(e) = self.validity.check(&self.name) {
+                if let Err(_e) = tx.send(Err(e)) {
+                  #[cfg(feature = "tracing")]
+                  tracing::error!(table_id=%id, table_name=%self.name, err=%_e, "failed to send write result");
+                }
+                continue;
+              }
+
+              // Safety: we are the only thread that writes the wal.
+              let wal = unsafe { &mut *self.wal.get() };
+
+              if let Err(_e) = tx.send(wal.insert_batch(id, self.seq.increment(), &batch)) {
+                #[cfg(feature = "tracing")]
+                tracing::error!(table_id=%id, table_name=%self.name, err=%_e, "failed to send write result");
+              }
             }
             Ok(Event::Remove { table_id, key, tx }) => {
               assert_eq!(id, table_id, "table({id})'s writer receive a write event of table({table_id}), please report this bug to https://github.com/al8n/lemondb/issues");
 
+              if let Err(e) = self.validity.check(&self.name) {
+                if let Err(_e) = tx.send(Err(e)) {
+                  #[cfg(feature = "tracing")]
+                  tracing::error!(table_id=%id, table_name=%self.name, err=%_e, "failed to send remove result");
+                }
+                continue;
+              }
+
               // Safety: we are the only thread that writes the wal.
               let wal = unsafe { &mut *self.wal.get() };
 
-              if let Err(_e) = tx.send(wal.remove(id, 0, &key)) {
+              if let Err(_e) = tx.send(wal.remove(id, self.seq.increment(), &key)) {
                 #[cfg(feature = "tracing")]
                 tracing::error!(table_id=%id, table_name=%self.name, err=%_e, "failed to send remove result");
               }
@@ -188,9 +279,9 @@ struct TableInner<C = Ascend> {
   id: TableId,
   wal: Arc<UnsafeCell<Wal<C>>>,
   write_tx: Sender<Event>,
+  seq: Arc<AtomicSeq>,
   manifest: Arc<Mutex<ManifestFile>>,
-  closed: AtomicBool,
-  removed: AtomicBool,
+  validity: Arc<TableValidity>,
   close_table_tx: Sender<()>,
   remove_table_tx: Sender<()>,
 }
@@ -199,6 +290,51 @@ struct TableInner<C = Ascend> {
 unsafe impl<C> Send for TableInner<C> {}
 unsafe impl<C> Sync for TableInner<C> {}
 
+/// A point-in-time snapshot of a single table's size and write
+/// backpressure, cheap to clone and intended to be polled frequently.
+///
+/// Modeled on OpenEthereum's `ClientReport` and Garage's metrics module:
+/// `live_keys`/`tombstones` and the log/value-log byte totals are the
+/// `state_db_mem`-style memory/disk accounting, while `pending_writes`
+/// exposes how close a standalone table is to filling its
+/// `write_buffer_size` bound (see [`StandaloneTableWriter`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TableStats {
+  /// Number of live (non-tombstone) keys in the table.
+  pub live_keys: u64,
+  /// Number of tombstones (removed keys not yet reclaimed) in the table.
+  pub tombstones: u64,
+  /// Number of key-log segments backing the table.
+  pub log_file_count: usize,
+  /// Bytes the key-log segments are currently using.
+  pub log_bytes: usize,
+  /// Bytes the key-log segments have reserved.
+  pub log_capacity: usize,
+  /// Bytes the table's value log has written so far.
+  pub vlog_bytes: u64,
+  /// Bytes the table's value log has reserved.
+  pub vlog_capacity: u64,
+  /// Number of events queued on the table's write channel, waiting for
+  /// its writer to apply them.
+  pub pending_writes: usize,
+}
+
+impl TableStats {
+  #[inline]
+  fn from_wal_stats(wal: WalStats, pending_writes: usize) -> Self {
+    Self {
+      live_keys: wal.live_keys,
+      tombstones: wal.tombstones,
+      log_file_count: wal.log_file_count,
+      log_bytes: wal.log_bytes,
+      log_capacity: wal.log_capacity,
+      vlog_bytes: wal.vlog_bytes,
+      vlog_capacity: wal.vlog_capacity,
+      pending_writes,
+    }
+  }
+}
+
 /// Table
 pub struct Table<C = Ascend> {
   inner: Arc<TableInner<C>>,
@@ -214,19 +350,148 @@ impl<C> Clone for Table<C> {
 
 impl<C: Comparator + Send + Sync + 'static> Table<C> {
   /// Returns `true` if the table contains the specified key.
+  ///
+  /// Reads as of the latest sequence number the table has observed; use
+  /// [`contains_at`](Self::contains_at) to pin the read to a [`Snapshot`].
   pub fn contains(&self, key: &[u8]) -> Result<bool, Error> {
     self.check_status()?;
     let wal = unsafe { &*self.inner.wal.get() };
-    wal.contains(0, key)
+    wal.contains(self.inner.seq.load(), key)
   }
 
   /// Get the value of the key.
+  ///
+  /// Reads as of the latest sequence number the table has observed; use
+  /// [`get_at`](Self::get_at) to pin the read to a [`Snapshot`].
   pub fn get(&self, key: &[u8]) -> Result<Option<crate::types::Entry>, Error> {
     self.check_status()?;
 
     let wal = unsafe { &*self.inner.wal.get() };
 
-    wal.get(0, key)
+    wal.get(self.inner.seq.load(), key)
+  }
+
+  /// Returns `true` if the table contained the specified key as of
+  /// `snapshot`, ignoring any write committed after it was taken.
+  pub fn contains_at(&self, key: &[u8], snapshot: &Snapshot) -> Result<bool, Error> {
+    self.check_status()?;
+    let wal = unsafe { &*self.inner.wal.get() };
+    wal.contains(snapshot.seq(), key)
+  }
+
+  /// Get the value of the key as of `snapshot`, ignoring any write
+  /// committed after it was taken.
+  pub fn get_at(
+    &self,
+    key: &[u8],
+    snapshot: &Snapshot,
+  ) -> Result<Option<crate::types::Entry>, Error> {
+    self.check_status()?;
+
+    let wal = unsafe { &*self.inner.wal.get() };
+
+    wal.get(snapshot.seq(), key)
+  }
+
+  /// Returns an ordered scan over `bounds`, as of the latest sequence
+  /// number the table has observed; use [`range_at`](Self::range_at) to pin
+  /// the read to a [`Snapshot`].
+  ///
+  /// Entries come back in the order this table's [`Comparator`] defines,
+  /// tombstones are skipped, and [`Iterator::rev`] flips the scan to
+  /// descending order. Modeled on Garage's K2V range reads (start key,
+  /// optional end, forward/reverse) and LevelDB's `LdbIterator`
+  /// (seek/next/prev/valid).
+  pub fn range(&self, bounds: impl RangeBounds<Bytes>) -> Result<TableIter<'_, C>, Error> {
+    self.range_at_seq(bounds, self.inner.seq.load())
+  }
+
+  /// Like [`range`](Self::range), but reads as of `snapshot` rather than
+  /// the latest sequence number.
+  pub fn range_at(
+    &self,
+    bounds: impl RangeBounds<Bytes>,
+    snapshot: &Snapshot,
+  ) -> Result<TableIter<'_, C>, Error> {
+    self.range_at_seq(bounds, snapshot.seq())
+  }
+
+  fn range_at_seq(
+    &self,
+    bounds: impl RangeBounds<Bytes>,
+    version: u64,
+  ) -> Result<TableIter<'_, C>, Error> {
+    self.check_status()?;
+    let start = bounds.start_bound().map(Bytes::clone);
+    let end = bounds.end_bound().map(Bytes::clone);
+    Ok(TableIter::new(self, version, start, end))
+  }
+
+  /// Returns an ordered scan over every entry in the table, as of the
+  /// latest sequence number the table has observed; use
+  /// [`iter_at`](Self::iter_at) to pin the read to a [`Snapshot`].
+  #[inline]
+  pub fn iter(&self) -> Result<TableIter<'_, C>, Error> {
+    self.range(..)
+  }
+
+  /// Like [`iter`](Self::iter), but reads as of `snapshot` rather than the
+  /// latest sequence number.
+  #[inline]
+  pub fn iter_at(&self, snapshot: &Snapshot) -> Result<TableIter<'_, C>, Error> {
+    self.range_at(.., snapshot)
+  }
+
+  /// Streams every live key-value pair in the table as of `snapshot`, for
+  /// moving or copying this table's contents into another [`Db`] via
+  /// [`Db::import_table`].
+  ///
+  /// Modeled on Mnesia's `send_table` receiver-loader protocol: the source
+  /// streams records out one at a time instead of collecting them into one
+  /// buffer, pinned to `snapshot` (see [`Db::snapshot`]) so the whole export
+  /// sees one consistent point-in-time view even if writes keep landing on
+  /// the table while it is still being drained.
+  #[inline]
+  pub fn export(
+    &self,
+    snapshot: &Snapshot,
+  ) -> Result<impl Iterator<Item = Result<(Bytes, Bytes), Error>> + '_, Error> {
+    Ok(self.iter_at(snapshot)?.map(|res| {
+      res.map(|ent| {
+        (
+          Bytes::copy_from_slice(ent.key()),
+          Bytes::copy_from_slice(ent.value()),
+        )
+      })
+    }))
+  }
+
+  /// Returns the entry with the smallest key, as of the latest sequence
+  /// number the table has observed.
+  #[inline]
+  pub fn first(&self) -> Result<Option<crate::types::Entry>, Error> {
+    self.iter()?.next().transpose()
+  }
+
+  /// Like [`first`](Self::first), but reads as of `snapshot` rather than
+  /// the latest sequence number.
+  #[inline]
+  pub fn first_at(&self, snapshot: &Snapshot) -> Result<Option<crate::types::Entry>, Error> {
+    self.iter_at(snapshot)?.next().transpose()
+  }
+
+  /// Returns the entry with the largest key, as of the latest sequence
+  /// number the table has observed.
+  #[inline]
+  pub fn last(&self) -> Result<Option<crate::types::Entry>, Error> {
+    self.iter()?.next_back().transpose()
+  }
+
+  /// Like [`last`](Self::last), but reads as of `snapshot` rather than the
+  /// latest sequence number.
+  #[inline]
+  pub fn last_at(&self, snapshot: &Snapshot) -> Result<Option<crate::types::Entry>, Error> {
+    self.iter_at(snapshot)?.next_back().transpose()
   }
 
   /// Insert a key-value pair into the table.
@@ -235,6 +500,34 @@ impl<C: Comparator + Send + Sync + 'static> Table<C> {
     self.insert_in(key, value)
   }
 
+  /// Writes a batch of puts and deletes to the table in one round trip.
+  /// `None` in the pair's second slot means the key is deleted, `Some`
+  /// means it is set to that value.
+  ///
+  /// The whole batch is handed to the table's writer as a single
+  /// [`Event::WriteBatch`] and applied to the WAL in one pass (see
+  /// [`Wal::insert_batch`]), so the caller gets one success/failure back
+  /// for every key in the batch instead of round-tripping the channel
+  /// once per key. Modeled on Garage's K2V batch endpoint.
+  pub fn write_batch(&self, batch: Vec<(Bytes, Option<Bytes>)>) -> Result<(), Error> {
+    self.check_status()?;
+
+    let (tx, rx) = oneshot::channel();
+    if let Err(_e) = self.inner.write_tx.send(Event::WriteBatch {
+      table_id: self.inner.id,
+      batch,
+      tx,
+    }) {
+      #[cfg(feature = "tracing")]
+      tracing::error!(table_id=%self.inner.id, table=%self.inner.name, err=%_e);
+    }
+
+    match rx.recv() {
+      Ok(res) => res,
+      Err(_) => Err(Error::TableClosed(self.inner.name.clone())),
+    }
+  }
+
   /// Remove a key from the table.
   pub fn remove(&self, key: Bytes) -> Result<(), Error> {
     self.check_status()?;
@@ -257,15 +550,21 @@ impl<C: Comparator + Send + Sync + 'static> Table<C> {
 
   #[inline]
   fn check_status(&self) -> Result<(), Error> {
-    if self.inner.closed.load(Ordering::Acquire) {
-      return Err(Error::TableClosed(self.inner.name.clone()));
-    }
-
-    if self.inner.removed.load(Ordering::Acquire) {
-      return Err(Error::TableRemoved(self.inner.name.clone()));
-    }
+    self.inner.validity.check(&self.inner.name)
+  }
 
-    Ok(())
+  /// Returns a snapshot of this table's size and write backpressure.
+  ///
+  /// Allocation-light so it is cheap to poll on a timer: everything but
+  /// the returned [`TableStats`] itself is read straight off existing
+  /// counters (see [`Wal::stats`]) and the write channel's queue depth.
+  pub fn stats(&self) -> Result<TableStats, Error> {
+    self.check_status()?;
+    let wal = unsafe { &*self.inner.wal.get() };
+    Ok(TableStats::from_wal_stats(
+      wal.stats(),
+      self.inner.write_tx.len(),
+    ))
   }
 
   fn insert_in(&self, key: Bytes, value: Bytes) -> Result<(), Error> {
@@ -293,11 +592,13 @@ impl<C: Comparator + Send + Sync + 'static> Table<C> {
     id: TableId,
     manifest: Arc<Mutex<ManifestFile>>,
     wal: Wal<C>,
+    seq: Arc<AtomicSeq>,
     write_ch: Either<Sender<Event>, usize>,
     ignore_writes_after_close: bool,
     shutdown_db_rx: Receiver<()>,
   ) -> Result<Self, Error> {
     let wal = Arc::new(UnsafeCell::new(wal));
+    let validity = Arc::new(TableValidity::new());
     let (close_table_tx, close_table_rx) = crossbeam_channel::bounded(1);
     let (remove_table_tx, remove_table_rx) = crossbeam_channel::bounded(1);
     match write_ch {
@@ -307,8 +608,8 @@ impl<C: Comparator + Send + Sync + 'static> Table<C> {
           id,
           wal: wal.clone(),
           write_tx: tx,
-          closed: AtomicBool::new(false),
-          removed: AtomicBool::new(false),
+          seq,
+          validity,
           manifest,
           close_table_tx,
           remove_table_tx,
@@ -323,8 +624,8 @@ impl<C: Comparator + Send + Sync + 'static> Table<C> {
             id,
             wal: wal.clone(),
             write_tx: tx,
-            closed: AtomicBool::new(false),
-            removed: AtomicBool::new(false),
+            seq: seq.clone(),
+            validity: validity.clone(),
             manifest,
             close_table_tx,
             remove_table_tx,
@@ -336,6 +637,8 @@ impl<C: Comparator + Send + Sync + 'static> Table<C> {
           id,
           rx,
           wal,
+          seq,
+          validity,
           ignore_writes_after_close,
           remove_table_rx,
           close_table_rx,
@@ -348,6 +651,89 @@ impl<C: Comparator + Send + Sync + 'static> Table<C> {
   }
 }
 
+/// An ordered, [`Comparator`]-aware scan over a [`Table`]'s entries,
+/// returned by [`Table::range`]/[`Table::iter`] and their `_at` variants.
+///
+/// Holds a read view of the WAL (`&*self.inner.wal.get()`) for the
+/// iterator's whole lifetime, so each step re-checks [`Table::check_status`]
+/// the same way a point [`Table::get`] would -- a table closed or removed
+/// mid-scan surfaces as [`Error::TableClosed`]/[`Error::TableRemoved`] from
+/// the next call to [`Iterator::next`]/[`DoubleEndedIterator::next_back`]
+/// rather than panicking or silently truncating the scan.
+pub struct TableIter<'a, C = Ascend> {
+  table: &'a Table<C>,
+  inner: crate::wal::lf::LogFileIterator<'a, C>,
+  start: Bound<Bytes>,
+  end: Bound<Bytes>,
+}
+
+impl<'a, C: Comparator + Send + Sync + 'static> TableIter<'a, C> {
+  fn new(table: &'a Table<C>, version: u64, start: Bound<Bytes>, end: Bound<Bytes>) -> Self {
+    let wal = unsafe { &*table.inner.wal.get() };
+    Self {
+      table,
+      inner: wal.iter(version),
+      start,
+      end,
+    }
+  }
+
+  fn in_bounds(&self, key: &[u8]) -> bool {
+    let wal = unsafe { &*self.table.inner.wal.get() };
+
+    let above_start = match &self.start {
+      Bound::Unbounded => true,
+      Bound::Included(b) => wal.compare(key, b) != core::cmp::Ordering::Less,
+      Bound::Excluded(b) => wal.compare(key, b) == core::cmp::Ordering::Greater,
+    };
+
+    above_start
+      && match &self.end {
+        Bound::Unbounded => true,
+        Bound::Included(b) => wal.compare(key, b) != core::cmp::Ordering::Greater,
+        Bound::Excluded(b) => wal.compare(key, b) == core::cmp::Ordering::Less,
+      }
+  }
+}
+
+impl<'a, C: Comparator + Send + Sync + 'static> Iterator for TableIter<'a, C> {
+  type Item = Result<crate::types::Entry, Error>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Err(e) = self.table.check_status() {
+        return Some(Err(e));
+      }
+
+      match self.inner.next() {
+        None => return None,
+        Some(ent) if self.in_bounds(ent.key()) => {
+          return Some(Ok(crate::types::Entry::new(ent.to_owned())))
+        }
+        Some(_) => continue,
+      }
+    }
+  }
+}
+
+impl<'a, C: Comparator + Send + Sync + 'static> DoubleEndedIterator for TableIter<'a, C> {
+  fn next_back(&mut self) -> Option<Self::Item> {
+    loop {
+      if let Err(e) = self.table.check_status() {
+        return Some(Err(e));
+      }
+
+      match self.inner.next_back() {
+        None => return None,
+        Some(ent) if self.in_bounds(ent.key()) => {
+          return Some(Ok(crate::types::Entry::new(ent.to_owned())))
+        }
+        Some(_) => continue,
+      }
+    }
+  }
+}
+
 enum Event {
   Write {
     table_id: TableId,
@@ -357,7 +743,7 @@ enum Event {
   },
   WriteBatch {
     table_id: TableId,
-    batch: Vec<(Bytes, Bytes)>,
+    batch: Vec<(Bytes, Option<Bytes>)>,
     tx: oneshot::Sender<Result<(), Error>>,
   },
   Remove {
@@ -375,6 +761,12 @@ pub struct Db<C = Ascend> {
   default_wal: Wal<C>,
   main_write_tx: Sender<Event>,
   main_write_rx: Receiver<Event>,
+  /// Hands out the sequence number every write across every table is
+  /// tagged with, so a [`Snapshot`] taken from one table's perspective is
+  /// still meaningful when read through another.
+  seq: Arc<AtomicSeq>,
+  /// The sequence numbers pinned by every live [`Snapshot`].
+  snapshots: Arc<SnapshotList>,
   cmp: Arc<C>,
   opts: Options,
   in_memory: Option<MemoryMode>,
@@ -382,16 +774,168 @@ pub struct Db<C = Ascend> {
   shutdown_rx: Receiver<()>,
 }
 
+/// An ordered multiset of the sequence numbers pinned by live
+/// [`Snapshot`]s, modeled on LevelDB's `SnapshotList`.
+///
+/// A compactor (when one exists in this tree) must not drop a version or
+/// tombstone whose sequence is `>=` [`SnapshotList::oldest`], since some
+/// live snapshot may still need to see it.
+#[derive(Default)]
+pub(crate) struct SnapshotList {
+  live: Mutex<BTreeMap<u64, usize>>,
+}
+
+impl SnapshotList {
+  fn register(&self, seq: u64) {
+    *self.live.lock_me().entry(seq).or_insert(0) += 1;
+  }
+
+  fn unregister(&self, seq: u64) {
+    let mut live = self.live.lock_me();
+    if let Some(count) = live.get_mut(&seq) {
+      *count -= 1;
+      if *count == 0 {
+        live.remove(&seq);
+      }
+    }
+  }
+
+  /// Returns the oldest live snapshot's sequence number, or `None` if
+  /// there are no live snapshots.
+  pub(crate) fn oldest(&self) -> Option<u64> {
+    self.live.lock_me().keys().next().copied()
+  }
+}
+
+/// A point-in-time, repeatable-read view across every table fed from the
+/// same [`Db`], pinned to the global sequence number in effect when it
+/// was taken.
+///
+/// [`Table::get_at`]/[`Table::contains_at`] resolve reads against a
+/// `Snapshot` instead of the table's latest sequence, so a write
+/// committed after the snapshot was taken -- including a key that was
+/// present when the snapshot was taken and removed afterwards -- stays
+/// invisible to it. Dropping a `Snapshot` unregisters it from the
+/// [`SnapshotList`] that a compactor consults to decide what is safe to
+/// collect.
+pub struct Snapshot {
+  seq: u64,
+  list: Arc<SnapshotList>,
+}
+
+impl Snapshot {
+  /// Returns the sequence number this snapshot pins reads to.
+  #[inline]
+  pub fn seq(&self) -> u64 {
+    self.seq
+  }
+}
+
+impl Drop for Snapshot {
+  fn drop(&mut self) {
+    self.list.unregister(self.seq);
+  }
+}
+
 impl Db {
-  /// Open a database with the given directory and options.
-  pub fn open<P>(dir: P, opts: Options) -> Result<Self, Error> {
-    if let Some(mode) = opts.in_memory {}
-    todo!()
+  /// Opens a database rooted at `dir`, recovering it from its on-disk
+  /// manifest and write-ahead logs.
+  ///
+  /// Mirrors LevelDB's open flow: the manifest is read first to learn which
+  /// tables and log segments exist, then each table's WAL is replayed in
+  /// order to repopulate its memtable before any write is accepted. A table
+  /// the manifest already flagged as removed is skipped rather than
+  /// reopened -- the manifest does not record where a removed table's files
+  /// live, so cleaning them up is left to whatever removed it.
+  pub fn open<P: AsRef<std::path::Path>>(dir: P, opts: Options) -> Result<Self, Error> {
+    Self::open_in(Some(dir.as_ref()), None, opts)
   }
 
-  /// Open a database in memory with the given options.
+  /// Opens an in-memory database, honoring `memory_mode` so that no file on
+  /// disk is ever touched.
   pub fn open_inmemory(memory_mode: MemoryMode, opts: Options) -> Result<Self, Error> {
-    todo!()
+    Self::open_in(None, Some(memory_mode), opts)
+  }
+
+  fn open_in(
+    dir: Option<&std::path::Path>,
+    memory_mode: Option<MemoryMode>,
+    opts: Options,
+  ) -> Result<Self, Error> {
+    let manifest_file = ManifestFile::open(dir, ManifestOptions::new())?;
+
+    // Continue fid/sequence allocation past whatever was already committed,
+    // so recovery never hands out an id or sequence that was live before
+    // this open.
+    let fid_generator = Arc::new(AtomicFid::new(manifest_file.last_fid().next()));
+    let seq = Arc::new(AtomicSeq::new(
+      manifest_file.with_manifest(|m| m.sequence()),
+    ));
+
+    let cmp = Arc::new(Ascend::default());
+    let manifest = Arc::new(Mutex::new(manifest_file));
+    let snapshots = Arc::new(SnapshotList::default());
+    let (main_write_tx, main_write_rx) = crossbeam_channel::unbounded();
+    let (shutdown_tx, shutdown_rx) = crossbeam_channel::bounded(0);
+
+    let wal_opts = opts.to_wal_options(memory_mode.is_some());
+
+    let default_wal = Wal::create(
+      fid_generator.increment(),
+      fid_generator.clone(),
+      manifest.clone(),
+      cmp.clone(),
+      wal_opts,
+    )?;
+
+    let file = manifest.lock_me();
+    let recovered: Result<std::vec::Vec<Table>, Error> = file.with_manifest(|m| {
+      m.tables()
+        .filter(|table_manifest| !table_manifest.is_removed())
+        .map(|table_manifest| {
+          let wal = Wal::open(
+            table_manifest,
+            fid_generator.clone(),
+            manifest.clone(),
+            cmp.clone(),
+            wal_opts,
+          )?;
+
+          Table::bootstrap(
+            table_manifest.name.clone(),
+            table_manifest.id,
+            manifest.clone(),
+            wal,
+            seq.clone(),
+            Either::Left(main_write_tx.clone()),
+            false,
+            shutdown_rx.clone(),
+          )
+        })
+        .collect()
+    });
+    drop(file);
+
+    let mut tables = HashMap::new();
+    for table in recovered? {
+      tables.insert(table.inner.id, table);
+    }
+
+    Ok(Self {
+      fid_generator,
+      manifest,
+      tables: Mutex::new(tables),
+      default_wal,
+      main_write_tx,
+      main_write_rx,
+      seq,
+      snapshots,
+      cmp,
+      opts,
+      in_memory: memory_mode,
+      shutdown_tx,
+      shutdown_rx,
+    })
   }
 
   /// Get a table with the given name. If this method returns `None`, then it means that the table either does not exist or has not been opened.
@@ -445,7 +989,7 @@ impl Db {
           self.fid_generator.clone(),
           self.manifest.clone(),
           self.cmp.clone(),
-          opts.to_wal_options(self.in_memory),
+          opts.to_wal_options(self.in_memory.is_some()),
         )?;
 
         let t = if opts.standalone {
@@ -454,6 +998,7 @@ impl Db {
             table_manifest.id,
             self.manifest.clone(),
             wal,
+            self.seq.clone(),
             Either::Right(opts.write_buffer_size()),
             opts.ignore_writes_after_close,
             self.shutdown_rx.clone(),
@@ -464,6 +1009,7 @@ impl Db {
             table_manifest.id,
             self.manifest.clone(),
             wal,
+            self.seq.clone(),
             Either::Left(self.main_write_tx.clone()),
             opts.ignore_writes_after_close,
             self.shutdown_rx.clone(),
@@ -492,7 +1038,7 @@ impl Db {
           self.fid_generator.clone(),
           self.manifest.clone(),
           self.cmp.clone(),
-          opts.to_wal_options(self.in_memory),
+          opts.to_wal_options(self.in_memory.is_some()),
         )?;
 
         // add table to manifest
@@ -510,6 +1056,7 @@ impl Db {
             table_id,
             self.manifest.clone(),
             wal,
+            self.seq.clone(),
             Either::Right(opts.write_buffer_size()),
             opts.ignore_writes_after_close,
             self.shutdown_rx.clone(),
@@ -520,6 +1067,7 @@ impl Db {
             table_id,
             self.manifest.clone(),
             wal,
+            self.seq.clone(),
             Either::Left(self.main_write_tx.clone()),
             opts.ignore_writes_after_close,
             self.shutdown_rx.clone(),
@@ -533,6 +1081,113 @@ impl Db {
     }
   }
 
+  /// Takes a repeatable-read snapshot over every table in the database,
+  /// pinned at the sequence number of the newest write committed so far.
+  ///
+  /// Pass the returned [`Snapshot`] to [`Table::get_at`]/
+  /// [`Table::contains_at`] to read as of this point in time regardless
+  /// of writes landing afterwards. Drop it once done to let a compactor
+  /// reclaim versions it was the last one holding onto.
+  #[inline]
+  pub fn snapshot(&self) -> Snapshot {
+    let seq = self.seq.load();
+    self.snapshots.register(seq);
+    Snapshot {
+      seq,
+      list: self.snapshots.clone(),
+    }
+  }
+
+  /// Writes batches across multiple tables in one call.
+  ///
+  /// `batches` is grouped by [`TableId`] and each group is sent to its
+  /// table as a single [`Event::WriteBatch`], so every key belonging to
+  /// the same table is applied atomically as one batch. Since every
+  /// non-standalone table already shares `main_write_tx` and its writer,
+  /// this is the natural place to later extend to a single cross-table
+  /// transaction; today each table's batch still succeeds or fails
+  /// independently of the others.
+  pub fn write_batch<I>(&self, batches: I) -> HashMap<TableId, Result<(), Error>>
+  where
+    I: IntoIterator<Item = (TableId, Vec<(Bytes, Option<Bytes>)>)>,
+  {
+    let mut grouped: HashMap<TableId, Vec<(Bytes, Option<Bytes>)>> = HashMap::new();
+    for (table_id, batch) in batches {
+      grouped.entry(table_id).or_default().extend(batch);
+    }
+
+    let tables = self.tables.lock_me();
+    grouped
+      .into_iter()
+      .map(|(table_id, batch)| {
+        let result = match tables.get(&table_id) {
+          Some(table) => table.write_batch(batch),
+          None => Err(Error::TableNotFound(std::format!("table#{table_id}").into())),
+        };
+        (table_id, result)
+      })
+      .collect()
+  }
+
+  /// Ingests a stream of key-value pairs produced by [`Table::export`] into
+  /// a table in this database, opening or creating it through the existing
+  /// [`open_table`](Self::open_table) manifest path first via
+  /// [`get_or_open_table`](Self::get_or_open_table).
+  ///
+  /// Paired with [`Table::export`] for moving or copying a table's live
+  /// contents into another `Db` -- an online backup, a shard relocation, or
+  /// cloning a table into a fresh database -- without taking either side
+  /// offline. The stream is drained and applied via [`Table::write_batch`]
+  /// in chunks of `chunk_size` entries, bounding how much of the export this
+  /// side ever holds in memory at once.
+  pub fn import_table<N, I>(
+    &self,
+    name: N,
+    opts: TableOptions,
+    chunk_size: usize,
+    stream: I,
+  ) -> Result<Table, Error>
+  where
+    N: Into<SmolStr>,
+    I: IntoIterator<Item = Result<(Bytes, Bytes), Error>>,
+  {
+    let table = self.get_or_open_table(name, opts)?;
+
+    let mut chunk = Vec::with_capacity(chunk_size);
+    for item in stream {
+      let (key, value) = item?;
+      chunk.push((key, Some(value)));
+      if chunk.len() >= chunk_size {
+        table.write_batch(std::mem::take(&mut chunk))?;
+      }
+    }
+
+    if !chunk.is_empty() {
+      table.write_batch(chunk)?;
+    }
+
+    Ok(table)
+  }
+
+  /// Returns a [`TableStats`] snapshot for every table currently open in
+  /// this database, keyed by [`TableId`].
+  ///
+  /// Modeled on OpenEthereum's `ClientReport` pattern of a cheaply
+  /// cloned, frequently polled stats struct: a caller can use the
+  /// per-table memtable/log byte totals to decide when to flush or close
+  /// an idle table, and `pending_writes` to see which standalone tables
+  /// are filling their `write_buffer_size` bound. A table whose stats
+  /// can't currently be read (e.g. it was just closed or removed) is
+  /// omitted rather than failing the whole report.
+  pub fn report(&self) -> HashMap<TableId, TableStats> {
+    self
+      .tables
+      .lock_me()
+      .iter()
+      .filter_map(|(id, table)| table.stats().ok().map(|stats| (*id, stats)))
+      .collect()
+  }
+
   /// Remove the table from the database. Returns `Ok(true)` if this call triggers the removal of the table and successfully remove ths table.
   /// Otherwise, if this method returns `Ok(false)`, then it means that the table is already removed or is in the process of being removed by another thread.
   ///
@@ -580,11 +1235,11 @@ impl Db {
         }
       }
       Some(t) => {
-        if t.inner.removed.fetch_or(true, Ordering::AcqRel) {
+        if t.inner.validity.removed.fetch_or(true, Ordering::AcqRel) {
           return Ok(false);
         }
 
-        t.inner.closed.store(true, Ordering::Release);
+        t.inner.validity.closed.store(true, Ordering::Release);
 
         if let Err(_e) = t.inner.remove_table_tx.send(()) {
           #[cfg(feature = "tracing")]
@@ -634,7 +1289,7 @@ impl Db {
     match tables.remove(&id) {
       None => Ok(()),
       Some(t) => {
-        if t.inner.closed.fetch_or(true, Ordering::AcqRel) {
+        if t.inner.validity.closed.fetch_or(true, Ordering::AcqRel) {
           return Ok(());
         }
 
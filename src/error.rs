@@ -19,6 +19,23 @@ impl core::fmt::Display for ChecksumMismatch {
   }
 }
 
+/// A value stored inline in the active log failed to decompress: the bytes
+/// are too short to carry the algorithm/length prefix [`Meta::set_compressed`]
+/// promises, or the compressed body itself is corrupt.
+///
+/// [`Meta::set_compressed`]: crate::types::Meta::set_compressed
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+#[cfg_attr(feature = "std", error("failed to decompress value"))]
+pub struct DecompressionFailed;
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for DecompressionFailed {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "failed to decompress value")
+  }
+}
+
 /// Errors that can occur when working with a log.
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(thiserror::Error))]
@@ -47,6 +64,11 @@ pub enum LogFileError {
   /// Returned when checksum mismatch.
   #[cfg_attr(feature = "std", error("checksum mismatch"))]
   ChecksumMismatch(#[cfg_attr(feature = "std", from)] ChecksumMismatch),
+
+  /// Returned when a compressed value stored inline in the active log fails
+  /// to decompress.
+  #[cfg_attr(feature = "std", error(transparent))]
+  Decompression(#[cfg_attr(feature = "std", from)] DecompressionFailed),
 }
 
 #[cfg(not(feature = "std"))]
@@ -56,11 +78,19 @@ impl From<skl::map::Error> for LogFileError {
   }
 }
 
+#[cfg(not(feature = "std"))]
+impl From<DecompressionFailed> for LogFileError {
+  fn from(e: DecompressionFailed) -> Self {
+    LogFileError::Decompression(e)
+  }
+}
+
 #[cfg(not(feature = "std"))]
 impl core::fmt::Display for LogFileError {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     match self {
       Self::ChecksumMismatch(e) => write!(f, "{e}"),
+      Self::Decompression(e) => write!(f, "{e}"),
       Self::Log(e) => write!(f, "{e}"),
       Self::WriteBatch { idx, source } => {
         write!(f, "failed to write batch at index {}: {}", idx, source)
@@ -70,66 +100,142 @@ impl core::fmt::Display for LogFileError {
 }
 
 /// Errors that can occur when encode/decode header.
-#[derive(Debug, thiserror::Error)]
-#[cfg(feature = "std")]
-#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum EncodeHeaderError {
   /// Buffer is too small to encode the value pointer.
-  #[error("buffer is too small to encode header")]
+  #[cfg_attr(feature = "std", error("buffer is too small to encode header"))]
   BufferTooSmall,
   /// Returned when encoding/decoding varint failed.
-  #[error("fail to decode header: {0}")]
-  VarintError(#[from] crate::util::VarintError),
+  #[cfg_attr(feature = "std", error("fail to decode header: {0}"))]
+  VarintError(#[cfg_attr(feature = "std", from)] crate::util::VarintError),
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for EncodeHeaderError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::BufferTooSmall => write!(f, "buffer is too small to encode header"),
+      Self::VarintError(e) => write!(f, "fail to decode header: {e}"),
+    }
+  }
 }
 
 /// Errors that can occur when encode/decode header.
-#[derive(Debug, thiserror::Error)]
-#[cfg(feature = "std")]
-#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum DecodeHeaderError {
   /// Not enough bytes to decode the value pointer.
-  #[error("not enough bytes to decode header")]
+  #[cfg_attr(feature = "std", error("not enough bytes to decode header"))]
   NotEnoughBytes,
   /// Returned when encoding/decoding varint failed.
-  #[error("fail to decode header: {0}")]
-  VarintError(#[from] crate::util::VarintError),
+  #[cfg_attr(feature = "std", error("fail to decode header: {0}"))]
+  VarintError(#[cfg_attr(feature = "std", from)] crate::util::VarintError),
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for DecodeHeaderError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::NotEnoughBytes => write!(f, "not enough bytes to decode header"),
+      Self::VarintError(e) => write!(f, "fail to decode header: {e}"),
+    }
+  }
+}
+
+/// Why an entry read back from the value log failed to validate.
+///
+/// Carried by [`ValueLogError::Corrupted`] alongside the offset the bad
+/// entry starts at, so a caller can log or recover (see
+/// [`ValueLogRecoveryMode`](crate::options::ValueLogRecoveryMode)) without
+/// having to re-derive what went wrong from scratch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CorruptionReason {
+  /// The entry's trailing CRC32 does not match its header, key and value
+  /// bytes.
+  ChecksumMismatch,
+  /// The entry's header claims more bytes than remain in the log; a crash
+  /// tore off the write mid-entry.
+  Truncated,
+  /// The stored bytes failed to decompress with the algorithm the header
+  /// names.
+  Decompression,
+}
+
+impl core::fmt::Display for CorruptionReason {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::ChecksumMismatch => write!(f, "checksum mismatch"),
+      Self::Truncated => write!(f, "entry truncated"),
+      Self::Decompression => write!(f, "decompression failed"),
+    }
+  }
 }
 
 /// Error type returned by the value log.
-#[cfg(feature = "std")]
-#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-#[derive(Debug, thiserror::Error)]
+///
+/// The [`Closed`](Self::Closed), [`ReadOnly`](Self::ReadOnly) and
+/// [`IO`](Self::IO) variants can only be produced by the mmap-backed
+/// storage, so `IO` is the only variant gated behind `std`. The file-header
+/// and uniform-layout variants ([`ChecksumMismatch`](Self::ChecksumMismatch),
+/// [`WrongMagic`](Self::WrongMagic), [`UnsupportedVersion`](Self::UnsupportedVersion),
+/// [`FidMismatch`](Self::FidMismatch), [`NotUniform`](Self::NotUniform) and
+/// [`UniformSizeMismatch`](Self::UniformSizeMismatch)) also come only from
+/// the file-backed mmap log; the rest are reachable from
+/// [`MemoryValueLog`](crate::wal::vlf::MemoryValueLog) on `alloc`-only
+/// targets too.
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum ValueLogError {
   /// An I/O error occurred.
-  #[error(transparent)]
+  #[cfg(feature = "std")]
+  #[cfg_attr(feature = "std", error(transparent))]
+  #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
   IO(#[from] std::io::Error),
 
   /// Returned when the value log is in closed status.
-  #[error("value log is closed")]
+  #[cfg_attr(feature = "std", error("value log is closed"))]
   Closed,
 
   /// Returned when trying to write to a read-only value log.
-  #[error("value log is read only")]
+  #[cfg_attr(feature = "std", error("value log is read only"))]
   ReadOnly,
 
   /// Returned when the value log checksum mismatch.
-  #[error("value log checksum mismatch")]
-  ChecksumMismatch(#[from] ChecksumMismatch),
+  #[cfg_attr(feature = "std", error("value log checksum mismatch"))]
+  ChecksumMismatch(#[cfg_attr(feature = "std", from)] ChecksumMismatch),
+
+  /// Returned when an entry fails to validate on read: its header or
+  /// trailing checksum doesn't match the bytes actually on disk.
+  #[cfg_attr(
+    feature = "std",
+    error("value log {fid} is corrupted at offset {offset}: {reason}")
+  )]
+  Corrupted {
+    /// The id of the value log the corrupt entry was read from.
+    fid: crate::Fid,
+    /// The offset of the corrupt entry within that log.
+    offset: u64,
+    /// Why the entry failed to validate.
+    reason: CorruptionReason,
+  },
 
-  /// Returned when the value log is corrupted.
-  #[error("value log is corrupted")]
-  Corrupted,
+  /// Returned when an entry header names a compression algorithm this build
+  /// does not recognize or was not compiled with support for.
+  #[cfg_attr(feature = "std", error("unsupported value log compression algorithm: {0}"))]
+  UnsupportedCompression(u8),
 
   /// Returned when fail to decode entry header from the value log.
-  #[error(transparent)]
-  DecodeHeader(#[from] DecodeHeaderError),
+  #[cfg_attr(feature = "std", error(transparent))]
+  DecodeHeader(#[cfg_attr(feature = "std", from)] DecodeHeaderError),
 
   /// Returned when fail to encode entry header.
-  #[error(transparent)]
-  EncodeHeader(#[from] EncodeHeaderError),
+  #[cfg_attr(feature = "std", error(transparent))]
+  EncodeHeader(#[cfg_attr(feature = "std", from)] EncodeHeaderError),
 
   /// Returned when the value log does not have enough space to hold the value.
-  #[error("value log does not have enough space to hold the value, required: {required}, remaining: {remaining}")]
+  #[cfg_attr(feature = "std", error("value log does not have enough space to hold the value, required: {required}, remaining: {remaining}"))]
   NotEnoughSpace {
     /// The required space.
     required: u64,
@@ -138,7 +244,7 @@ pub enum ValueLogError {
   },
 
   /// Returned when the value offset is out of bound.
-  #[error("value offset is out of value log bound, offset: {offset}, len: {len}, size: {size}")]
+  #[cfg_attr(feature = "std", error("value offset is out of value log bound, offset: {offset}, len: {len}, size: {size}"))]
   OutOfBound {
     /// The value offset.
     offset: usize,
@@ -147,6 +253,136 @@ pub enum ValueLogError {
     /// The value log size.
     size: u64,
   },
+
+  /// Returned when a value log's leading header does not start with the
+  /// expected magic bytes, i.e. the file is not a value log at all (wrong
+  /// path, truncated, or from something else entirely).
+  #[cfg_attr(feature = "std", error("value log header has the wrong magic bytes"))]
+  WrongMagic,
+
+  /// Returned when a value log's header names a format version this build
+  /// does not know how to read.
+  #[cfg_attr(feature = "std", error("unsupported value log format version: {0}"))]
+  UnsupportedVersion(u8),
+
+  /// Returned when a value log's header names a file id different from the
+  /// one the caller asked to open, i.e. the file at that path was swapped
+  /// or renamed out from under the caller.
+  #[cfg_attr(
+    feature = "std",
+    error("value log header names fid {found}, but {expected} was expected")
+  )]
+  FidMismatch {
+    /// The fid the caller asked to open.
+    expected: crate::Fid,
+    /// The fid actually recorded in the header.
+    found: crate::Fid,
+  },
+
+  /// Returned by a uniform-layout write/read call against a value log that
+  /// was not [`CreateOptions::with_uniform`](crate::options::CreateOptions)-configured.
+  #[cfg_attr(feature = "std", error("value log is not in uniform-record mode"))]
+  NotUniform,
+
+  /// Returned when a uniform-layout write's value is not exactly the
+  /// declared record size.
+  #[cfg_attr(
+    feature = "std",
+    error("value log uniform record size is {expected}, got {found}")
+  )]
+  UniformSizeMismatch {
+    /// The declared uniform record size.
+    expected: u64,
+    /// The size of the value actually supplied.
+    found: u64,
+  },
+
+  /// Returned by a growable value log (see [`MemoryValueLog::grow`](crate::wal::vlf::memory::MemoryValueLog::grow))
+  /// instead of [`Self::NotEnoughSpace`] when the write that didn't fit
+  /// could still succeed if the caller grows the log by `additional` bytes
+  /// and retries, because doing so would stay within [`CreateOptions::max_size`](crate::options::CreateOptions)
+  /// (or no ceiling was set at all).
+  #[cfg_attr(
+    feature = "std",
+    error("value log needs to grow by {additional} bytes before this write can succeed")
+  )]
+  NeedsGrow {
+    /// How many additional bytes the log needs before the write that
+    /// triggered this error would fit.
+    additional: u64,
+  },
+}
+
+#[cfg(not(feature = "std"))]
+impl From<ChecksumMismatch> for ValueLogError {
+  fn from(e: ChecksumMismatch) -> Self {
+    Self::ChecksumMismatch(e)
+  }
+}
+
+#[cfg(not(feature = "std"))]
+impl From<DecodeHeaderError> for ValueLogError {
+  fn from(e: DecodeHeaderError) -> Self {
+    Self::DecodeHeader(e)
+  }
+}
+
+#[cfg(not(feature = "std"))]
+impl From<EncodeHeaderError> for ValueLogError {
+  fn from(e: EncodeHeaderError) -> Self {
+    Self::EncodeHeader(e)
+  }
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for ValueLogError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Self::Closed => write!(f, "value log is closed"),
+      Self::ReadOnly => write!(f, "value log is read only"),
+      Self::ChecksumMismatch(e) => write!(f, "{e}"),
+      Self::Corrupted {
+        fid,
+        offset,
+        reason,
+      } => write!(f, "value log {fid} is corrupted at offset {offset}: {reason}"),
+      Self::UnsupportedCompression(algo) => {
+        write!(f, "unsupported value log compression algorithm: {algo}")
+      }
+      Self::DecodeHeader(e) => write!(f, "{e}"),
+      Self::EncodeHeader(e) => write!(f, "{e}"),
+      Self::NotEnoughSpace {
+        required,
+        remaining,
+      } => write!(
+        f,
+        "value log does not have enough space to hold the value, required: {}, remaining: {}",
+        required, remaining
+      ),
+      Self::OutOfBound { offset, len, size } => write!(
+        f,
+        "value offset is out of value log bound, offset: {}, len: {}, size: {}",
+        offset, len, size
+      ),
+      Self::WrongMagic => write!(f, "value log header has the wrong magic bytes"),
+      Self::UnsupportedVersion(v) => write!(f, "unsupported value log format version: {v}"),
+      Self::FidMismatch { expected, found } => write!(
+        f,
+        "value log header names fid {found}, but {expected} was expected"
+      ),
+      Self::NotUniform => write!(f, "value log is not in uniform-record mode"),
+      Self::UniformSizeMismatch { expected, found } => write!(
+        f,
+        "value log uniform record size is {}, got {}",
+        expected, found
+      ),
+      Self::NeedsGrow { additional } => write!(
+        f,
+        "value log needs to grow by {} bytes before this write can succeed",
+        additional
+      ),
+    }
+  }
 }
 
 /// Errors that can occur when working with database
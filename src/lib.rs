@@ -32,6 +32,9 @@ mod cache;
 mod manifest;
 mod wal;
 
+#[cfg(feature = "std")]
+mod archive;
+
 mod db;
 pub use db::*;
 
@@ -13,7 +13,11 @@ use aol::{CustomFlags, Entry};
 use hashbrown::{HashMap, HashSet};
 use smol_str::SmolStr;
 
-use crate::{options::ManifestOptions, util::VarintError, Fid, TableId};
+use crate::{
+  options::{CompressionType, ManifestOptions, ManifestRecoveryMode},
+  util::VarintError,
+  Fid, TableId,
+};
 
 mod disk;
 mod memory;
@@ -83,6 +87,20 @@ pub(crate) enum ManifestRecordError {
   /// Unknown manifest event.
   #[cfg_attr(feature = "std", error("unknown manifest record type: {0}"))]
   UnknownManifestRecordType(u8),
+  /// A decoded `Fid`/`TableId` overflowed the range its type can represent,
+  /// e.g. a corrupt record whose varint is wider than `u16::MAX` for a
+  /// `TableId`. Recovery fails loudly here instead of silently truncating
+  /// the id.
+  #[cfg_attr(
+    feature = "std",
+    error("decoded id {actual} overflows the maximum of {max}")
+  )]
+  IdOverflow {
+    /// The largest value the id type can represent.
+    max: u64,
+    /// The value actually decoded off the wire.
+    actual: u64,
+  },
 }
 
 impl ManifestRecordError {
@@ -93,6 +111,14 @@ impl ManifestRecordError {
       VarintError::BufferTooSmall => Self::BufferTooSmall,
     }
   }
+
+  #[inline]
+  const fn from_decode_error(e: crate::types::DecodeError) -> Self {
+    match e {
+      crate::types::DecodeError::IdOverflow { max, actual } => Self::IdOverflow { max, actual },
+      crate::types::DecodeError::Varint(e) => Self::from_varint_error(e),
+    }
+  }
 }
 
 /// Errors for manifest file.
@@ -151,20 +177,55 @@ impl std::error::Error for ManifestFileError {}
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub(super) enum ManifestRecord {
-  Log { fid: Fid, tid: TableId },
+  Log {
+    fid: Fid,
+    tid: TableId,
+    codec: CompressionType,
+  },
   Table { id: TableId, name: SmolStr },
+  /// Persists the high-water marks for the fid/table-id allocators.
+  ///
+  /// Written as the first record of every rewritten manifest, so that a
+  /// rewrite, which only re-emits records for tables/logs still alive, does
+  /// not lose the counters. No `Metadata` record is appended as the
+  /// counters advance between rewrites.
+  Metadata {
+    next_fid: Fid,
+    next_table_id: TableId,
+    /// The latest sequence number committed at the time this record was
+    /// written, so sequence numbers never regress across a restart.
+    sequence: u64,
+  },
 }
 
 impl ManifestRecord {
   #[inline]
   pub(super) fn log(fid: Fid, tid: TableId) -> Self {
-    Self::Log { fid, tid }
+    Self::Log {
+      fid,
+      tid,
+      codec: CompressionType::None,
+    }
+  }
+
+  #[inline]
+  pub(super) fn log_with_codec(fid: Fid, tid: TableId, codec: CompressionType) -> Self {
+    Self::Log { fid, tid, codec }
   }
 
   #[inline]
   pub(super) fn table(table_id: TableId, name: SmolStr) -> Self {
     Self::Table { id: table_id, name }
   }
+
+  #[inline]
+  pub(super) fn metadata(next_fid: Fid, next_table_id: TableId, sequence: u64) -> Self {
+    Self::Metadata {
+      next_fid,
+      next_table_id,
+      sequence,
+    }
+  }
 }
 
 #[cfg(feature = "std")]
@@ -173,8 +234,13 @@ impl aol::Record for ManifestRecord {
 
   fn encoded_size(&self) -> usize {
     match self {
-      Self::Log { fid, tid, .. } => 1 + fid.encoded_len() + tid.encoded_len(),
+      Self::Log { fid, tid, .. } => 1 + fid.encoded_len() + tid.encoded_len() + 1,
       Self::Table { id, name } => 1 + id.encoded_len() + mem::size_of::<u8>() + name.len(),
+      Self::Metadata {
+        next_fid,
+        next_table_id,
+        ..
+      } => 1 + next_fid.encoded_len() + next_table_id.encoded_len() + mem::size_of::<u64>(),
     }
   }
 
@@ -185,7 +251,7 @@ impl aol::Record for ManifestRecord {
     }
 
     match self {
-      Self::Log { fid, tid, .. } => {
+      Self::Log { fid, tid, codec } => {
         let mut cur = 0;
         buf[cur] = 0;
         cur += 1;
@@ -195,6 +261,8 @@ impl aol::Record for ManifestRecord {
         cur += tid
           .encode(&mut buf[cur..])
           .map_err(Self::Error::from_varint_error)?;
+        buf[cur] = *codec as u8;
+        cur += 1;
         Ok(cur)
       }
       Self::Table { id, name } => {
@@ -215,6 +283,24 @@ impl aol::Record for ManifestRecord {
         cur += name.len();
         Ok(cur)
       }
+      Self::Metadata {
+        next_fid,
+        next_table_id,
+        sequence,
+      } => {
+        let mut cur = 0;
+        buf[cur] = 2;
+        cur += 1;
+        cur += next_fid
+          .encode(&mut buf[cur..])
+          .map_err(Self::Error::from_varint_error)?;
+        cur += next_table_id
+          .encode(&mut buf[cur..])
+          .map_err(Self::Error::from_varint_error)?;
+        buf[cur..cur + mem::size_of::<u64>()].copy_from_slice(&sequence.to_le_bytes());
+        cur += mem::size_of::<u64>();
+        Ok(cur)
+      }
     }
   }
 
@@ -227,19 +313,25 @@ impl aol::Record for ManifestRecord {
     let mut cur = 1;
     Ok(match kind {
       0 => {
-        let (n, fid) = Fid::decode(&buf[cur..]).map_err(Self::Error::from_varint_error)?;
+        let (n, fid) = Fid::decode(&buf[cur..]).map_err(Self::Error::from_decode_error)?;
         cur += n;
-        let (n, tid) = TableId::decode(&buf[cur..]).map_err(Self::Error::from_varint_error)?;
+        let (n, tid) = TableId::decode(&buf[cur..]).map_err(Self::Error::from_decode_error)?;
         cur += n;
         // if n is larger than max u16 varint size, it's corrupted
         if n > 3 {
           return Err(Self::Error::Corrupted);
         }
 
-        (cur, Self::Log { fid, tid })
+        if buf.len() <= cur {
+          return Err(Self::Error::NotEnoughBytes);
+        }
+        let codec = CompressionType::from_u8(buf[cur]);
+        cur += 1;
+
+        (cur, Self::Log { fid, tid, codec })
       }
       1 => {
-        let (n, id) = TableId::decode(&buf[cur..]).map_err(Self::Error::from_varint_error)?;
+        let (n, id) = TableId::decode(&buf[cur..]).map_err(Self::Error::from_decode_error)?;
 
         // if n is larger than max u16 varint size, it's corrupted
         if n > 3 {
@@ -257,6 +349,33 @@ impl aol::Record for ManifestRecord {
         cur += len;
         (cur, Self::Table { id, name })
       }
+      2 => {
+        let (n, next_fid) = Fid::decode(&buf[cur..]).map_err(Self::Error::from_decode_error)?;
+        cur += n;
+        let (n, next_table_id) =
+          TableId::decode(&buf[cur..]).map_err(Self::Error::from_decode_error)?;
+        cur += n;
+        if n > 3 {
+          return Err(Self::Error::Corrupted);
+        }
+
+        if buf.len() < cur + mem::size_of::<u64>() {
+          return Err(Self::Error::NotEnoughBytes);
+        }
+        let mut sequence_bytes = [0u8; mem::size_of::<u64>()];
+        sequence_bytes.copy_from_slice(&buf[cur..cur + mem::size_of::<u64>()]);
+        let sequence = u64::from_le_bytes(sequence_bytes);
+        cur += mem::size_of::<u64>();
+
+        (
+          cur,
+          Self::Metadata {
+            next_fid,
+            next_table_id,
+            sequence,
+          },
+        )
+      }
       _ => return Err(Self::Error::UnknownManifestRecordType(kind)),
     })
   }
@@ -354,11 +473,13 @@ impl ManifestEntry {
 #[viewit::viewit(getters(skip), setters(skip))]
 #[derive(Debug)]
 pub(crate) struct TableManifest {
-  name: SmolStr,
-  id: TableId,
-  removed: bool,
-  vlogs: BTreeSet<Fid>,
-  logs: HashSet<Fid>,
+  pub(crate) name: SmolStr,
+  pub(crate) id: TableId,
+  pub(crate) removed: bool,
+  pub(crate) vlogs: BTreeSet<Fid>,
+  pub(crate) logs: HashSet<Fid>,
+  /// The compression codec each log/vlog fid was written with.
+  codecs: HashMap<Fid, CompressionType>,
 }
 
 impl TableManifest {
@@ -369,6 +490,7 @@ impl TableManifest {
       id,
       vlogs: BTreeSet::new(),
       logs: HashSet::new(),
+      codecs: HashMap::new(),
       removed: false,
     }
   }
@@ -381,9 +503,29 @@ impl TableManifest {
 
   /// Returns `true` if the table is marked as removed.
   #[inline]
-  const fn is_removed(&self) -> bool {
+  pub(crate) const fn is_removed(&self) -> bool {
     self.removed
   }
+
+  /// Returns the fids of this table's value logs (frozen `.vlog` segments).
+  #[inline]
+  pub(crate) fn value_logs(&self) -> impl Iterator<Item = Fid> + '_ {
+    self.vlogs.iter().copied()
+  }
+
+  /// Returns the fids of this table's frozen (immutable) logs.
+  #[inline]
+  pub(crate) fn frozen_logs(&self) -> impl Iterator<Item = Fid> + '_ {
+    self.logs.iter().copied()
+  }
+
+  /// Returns the compression codec `fid` was written with, if `fid` belongs
+  /// to this table, as named by its `Log` record. Returns
+  /// `CompressionType::None` for a fid this table has no record of.
+  #[inline]
+  pub(crate) fn codec(&self, fid: Fid) -> CompressionType {
+    self.codecs.get(&fid).copied().unwrap_or_default()
+  }
 }
 
 #[derive(Debug, Default)]
@@ -391,6 +533,9 @@ pub(crate) struct Manifest {
   tables: HashMap<TableId, TableManifest>,
   last_fid: Fid,
   last_table_id: TableId,
+  /// The latest sequence number committed, restored from the manifest's
+  /// `Metadata` record on open so it never regresses across a restart.
+  sequence: u64,
 
   // Contains total number of creation and deletion changes in the manifest -- used to compute
   // whether it'd be useful to rewrite the manifest.
@@ -411,6 +556,14 @@ impl Manifest {
     self.tables.values().find(|table| table.name.eq(name))
   }
 
+  /// Returns every table this manifest currently tracks, including ones
+  /// already flagged as removed; [`Db::open`](crate::sync::Db::open)
+  /// filters those out rather than reopening them.
+  #[inline]
+  pub(crate) fn tables(&self) -> impl Iterator<Item = &TableManifest> {
+    self.tables.values()
+  }
+
   fn validate_in(&self, entry: &aol::Entry<ManifestRecord>) -> Result<(), ManifestError> {
     let flag = entry.flag();
     match entry.data() {
@@ -452,6 +605,9 @@ impl Manifest {
           Err(ManifestError::TableNotFound(*tid))
         }
       }
+      // A metadata record only ever advances the high-water marks, so it's
+      // always valid to apply regardless of the tables currently tracked.
+      ManifestRecord::Metadata { .. } => Ok(()),
     }
   }
 
@@ -460,13 +616,14 @@ impl Manifest {
     let record = entry.into_data();
 
     match record {
-      ManifestRecord::Log { fid, tid } => {
+      ManifestRecord::Log { fid, tid, codec } => {
         if let Some(table) = self.tables.get_mut(&tid) {
           if flag.custom_flag().bit1() {
             table.vlogs.insert(fid);
           } else {
             table.logs.insert(fid);
           }
+          table.codecs.insert(fid, codec);
           Ok(())
         } else {
           Err(ManifestError::TableNotFound(tid))
@@ -483,10 +640,98 @@ impl Manifest {
           Err(ManifestError::TableNotFound(id))
         }
       }
+      ManifestRecord::Metadata {
+        next_fid,
+        next_table_id,
+        sequence,
+      } => {
+        self.last_fid = self.last_fid.max(next_fid);
+        self.last_table_id = self.last_table_id.max(next_table_id);
+        self.sequence = self.sequence.max(sequence);
+        Ok(())
+      }
+    }
+  }
+
+  /// Returns a `Metadata` record describing the current high-water marks, to
+  /// be written as the first record of a rewritten manifest.
+  #[inline]
+  pub(super) fn metadata_record(&self) -> ManifestRecord {
+    ManifestRecord::metadata(self.last_fid, self.last_table_id, self.sequence)
+  }
+
+  /// Returns the latest committed sequence number restored from the
+  /// manifest.
+  #[inline]
+  pub(crate) fn sequence(&self) -> u64 {
+    self.sequence
+  }
+}
+
+/// Tracks the set of sequence numbers currently pinned by open snapshots, so
+/// compaction/log-dropping logic can tell which sequence is the oldest one a
+/// reader might still observe and avoid reclaiming data still visible to it.
+///
+/// Modeled on LevelDB's `SnapshotList`: each pin increments a reference count
+/// for its sequence number; `oldest()` is the floor below which nothing is
+/// observable anymore, and it collapses back to `None` once the list empties.
+#[derive(Debug, Default)]
+pub(crate) struct SnapshotList {
+  pinned: parking_lot::Mutex<std::collections::BTreeMap<u64, usize>>,
+}
+
+impl SnapshotList {
+  #[inline]
+  pub(crate) fn new() -> Self {
+    Self::default()
+  }
+
+  /// Pins `sequence`, returning a guard that unpins it on drop.
+  pub(crate) fn pin(self: &std::sync::Arc<Self>, sequence: u64) -> SnapshotHandle {
+    *self.pinned.lock().entry(sequence).or_insert(0) += 1;
+    SnapshotHandle {
+      list: self.clone(),
+      sequence,
+    }
+  }
+
+  /// Returns the oldest sequence number still pinned by a live snapshot, if
+  /// any.
+  pub(crate) fn oldest(&self) -> Option<u64> {
+    self.pinned.lock().keys().next().copied()
+  }
+
+  fn unpin(&self, sequence: u64) {
+    let mut pinned = self.pinned.lock();
+    if let std::collections::btree_map::Entry::Occupied(mut e) = pinned.entry(sequence) {
+      *e.get_mut() -= 1;
+      if *e.get() == 0 {
+        e.remove();
+      }
     }
   }
 }
 
+/// A handle to a pinned sequence number. Unpins it automatically on drop.
+pub(crate) struct SnapshotHandle {
+  list: std::sync::Arc<SnapshotList>,
+  sequence: u64,
+}
+
+impl SnapshotHandle {
+  /// The sequence number this handle pins.
+  #[inline]
+  pub(crate) const fn sequence(&self) -> u64 {
+    self.sequence
+  }
+}
+
+impl Drop for SnapshotHandle {
+  fn drop(&mut self) {
+    self.list.unpin(self.sequence);
+  }
+}
+
 #[derive(derive_more::From)]
 enum ManifestFileKind {
   Memory(memory::MemoryManifest),
@@ -1,17 +1,64 @@
-use std::{fs::OpenOptions, path::Path};
+use std::{
+  fs::{self, OpenOptions},
+  io,
+  path::{Path, PathBuf},
+};
 
 use aol::{
   fs::{AppendLog, Error, Options},
-  Entry,
+  CustomFlags, Entry,
 };
 use parking_lot::Mutex;
 
-use crate::Fid;
+use crate::{types::AtomicFid, Fid};
 
 use super::*;
 
+/// The name of the pointer file that names the currently active manifest file.
+///
+/// This mirrors the `CURRENT` file used by LevelDB's `version_set`: it is the
+/// only file whose name never changes, so recovery always has a stable place
+/// to start from even if a rotation crashes halfway through.
+const CURRENT_FILENAME: &str = "CURRENT";
+
+/// The legacy, pre-rotation manifest filename. Kept around so that databases
+/// created before this scheme was introduced can still be opened: on open,
+/// a bare `MANIFEST` file with no `CURRENT` pointer is adopted as `MANIFEST-0`.
 const MANIFEST_FILENAME: &str = "MANIFEST";
 
+const MANIFEST_PREFIX: &str = "MANIFEST-";
+
+#[inline]
+fn manifest_filename(fid: Fid) -> String {
+  std::format!("{MANIFEST_PREFIX}{fid}")
+}
+
+/// Reads the `CURRENT` file and returns the manifest filename it names, if any.
+fn read_current(dir: &Path) -> io::Result<Option<String>> {
+  match fs::read_to_string(dir.join(CURRENT_FILENAME)) {
+    Ok(contents) => Ok(Some(contents.trim().to_string())),
+    Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+    Err(e) => Err(e),
+  }
+}
+
+/// Atomically repoints `CURRENT` at `filename`: write to a temporary file in
+/// the same directory, fsync it, then rename it over `CURRENT` (rename is
+/// atomic on the same filesystem), and finally fsync the directory so the
+/// rename itself is durable.
+fn write_current(dir: &Path, filename: &str) -> io::Result<()> {
+  let tmp_path = dir.join(std::format!("{CURRENT_FILENAME}.tmp"));
+  {
+    let tmp = fs::File::create(&tmp_path)?;
+    use io::Write;
+    (&tmp).write_all(filename.as_bytes())?;
+    tmp.sync_all()?;
+  }
+  fs::rename(&tmp_path, dir.join(CURRENT_FILENAME))?;
+  fs::File::open(dir)?.sync_all()?;
+  Ok(())
+}
+
 impl aol::fs::Snapshot for Manifest {
   type Record = ManifestRecord;
 
@@ -24,6 +71,7 @@ impl aol::fs::Snapshot for Manifest {
       tables: HashMap::new(),
       last_fid: Fid::new(0),
       last_table_id: TableId::new(0),
+      sequence: 0,
       creations: 0,
       deletions: 0,
       opts,
@@ -46,8 +94,10 @@ impl aol::fs::Snapshot for Manifest {
   }
 
   fn clear(&mut self) -> Result<(), Self::Error> {
+    // Counters must never regress across a rewrite: only the live table/log
+    // set is cleared, `last_fid`/`last_table_id` (restored from the
+    // `Metadata` record on open) are left untouched.
     self.tables.clear();
-    self.last_fid = Fid::new(0);
     self.creations = 0;
     self.deletions = 0;
     Ok(())
@@ -55,29 +105,122 @@ impl aol::fs::Snapshot for Manifest {
 }
 
 pub(super) struct DiskManifest {
+  dir: PathBuf,
   log: Mutex<AppendLog<Manifest>>,
+  rewrite_threshold: usize,
+  version: u16,
+  /// The fid of the `MANIFEST-<fid>` file currently pointed at by `CURRENT`.
+  current_fid: AtomicFid,
 }
 
 impl DiskManifest {
   /// Open and replay the manifest file.
+  ///
+  /// The live manifest is named `MANIFEST-<fid>`; which one is live is
+  /// recorded in the tiny `CURRENT` file. If `CURRENT` is missing (a
+  /// database created before this scheme existed, or a fresh directory), a
+  /// bare `MANIFEST` is adopted as `MANIFEST-0`, and a fresh directory is
+  /// bootstrapped straight onto `MANIFEST-0`.
   pub(super) fn open<P: AsRef<Path>>(
     path: P,
     rewrite_threshold: usize,
     version: u16,
+    recovery_mode: ManifestRecoveryMode,
   ) -> Result<Self, Error<Manifest>> {
-    let path = path.as_ref().join(MANIFEST_FILENAME);
+    let dir = path.as_ref().to_path_buf();
+    fs::create_dir_all(&dir).map_err(aol::fs::Error::from)?;
+
+    let (current_fid, filename) = match read_current(&dir).map_err(aol::fs::Error::from)? {
+      Some(name) => {
+        let fid = name
+          .strip_prefix(MANIFEST_PREFIX)
+          .and_then(|s| s.parse::<u64>().ok())
+          .map(Fid::new)
+          .unwrap_or(Fid::new(0));
+        (fid, name)
+      }
+      None if dir.join(MANIFEST_FILENAME).exists() => {
+        // Adopt a pre-rotation manifest as `MANIFEST-0`.
+        let name = manifest_filename(Fid::new(0));
+        fs::rename(dir.join(MANIFEST_FILENAME), dir.join(&name)).map_err(aol::fs::Error::from)?;
+        write_current(&dir, &name).map_err(aol::fs::Error::from)?;
+        (Fid::new(0), name)
+      }
+      None => {
+        let name = manifest_filename(Fid::new(0));
+        write_current(&dir, &name).map_err(aol::fs::Error::from)?;
+        (Fid::new(0), name)
+      }
+    };
+
+    let full_path = dir.join(&filename);
+    let log = match Self::open_append_log(&full_path, rewrite_threshold, version) {
+      Ok(log) => log,
+      Err(e) if recovery_mode == ManifestRecoveryMode::Tolerant => {
+        Self::recover_torn_tail(&full_path, rewrite_threshold, version, e)?
+      }
+      Err(e) => return Err(e),
+    };
+
+    Ok(Self {
+      dir,
+      log: Mutex::new(log),
+      rewrite_threshold,
+      version,
+      current_fid: AtomicFid::new(current_fid),
+    })
+  }
+
+  fn open_append_log(
+    path: &Path,
+    rewrite_threshold: usize,
+    version: u16,
+  ) -> Result<AppendLog<Manifest>, Error<Manifest>> {
     let mut open_options = OpenOptions::new();
     open_options.read(true).create(true).append(true);
-    let log = AppendLog::open(
-      &path,
+    AppendLog::open(
+      path,
       ManifestOptions::new().with_rewrite_threshold(rewrite_threshold),
       open_options,
       Options::new().with_magic_version(version),
-    )?;
+    )
+  }
 
-    Ok(Self {
-      log: Mutex::new(log),
-    })
+  /// Recovers from a torn trailing record by repeatedly truncating the file
+  /// one byte shorter and retrying the open, stopping at the first prefix
+  /// that replays cleanly. Mirrors LevelDB's manifest `LogReader`, which
+  /// stops cleanly at the first truncated/corrupt record instead of failing
+  /// the whole open. Returns the recovered log; the caller's original error
+  /// is returned instead if no valid prefix is found.
+  fn recover_torn_tail(
+    path: &Path,
+    rewrite_threshold: usize,
+    version: u16,
+    original_err: Error<Manifest>,
+  ) -> Result<AppendLog<Manifest>, Error<Manifest>> {
+    let len = fs::metadata(path).map_err(aol::fs::Error::from)?.len();
+    let mut candidate = len;
+
+    while candidate > 0 {
+      candidate -= 1;
+      let file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .map_err(aol::fs::Error::from)?;
+      file.set_len(candidate).map_err(aol::fs::Error::from)?;
+      drop(file);
+
+      if let Ok(log) = Self::open_append_log(path, rewrite_threshold, version) {
+        tracing::warn!(
+          path = %path.display(),
+          offset = candidate,
+          "manifest torn trailing record truncated during recovery"
+        );
+        return Ok(log);
+      }
+    }
+
+    Err(original_err)
   }
 
   #[inline]
@@ -97,4 +240,81 @@ impl DiskManifest {
   pub(super) fn last_fid(&self) -> Fid {
     self.log.lock().snapshot().last_fid
   }
+
+  #[inline]
+  pub(super) fn sequence(&self) -> u64 {
+    self.log.lock().snapshot().sequence
+  }
+
+  /// Applies `f` to the current in-memory manifest snapshot, holding the
+  /// underlying lock only for the duration of the call.
+  #[inline]
+  pub(super) fn with_manifest<R>(&self, f: impl FnOnce(&Manifest) -> R) -> R {
+    f(self.log.lock().snapshot())
+  }
+
+  /// Streams the current in-memory snapshot into a brand-new
+  /// `MANIFEST-<newfid>`, fsyncs it, atomically repoints `CURRENT` at it,
+  /// fsyncs the directory, and finally unlinks the old manifest file.
+  ///
+  /// This replaces an in-place full rewrite with the LevelDB scheme: there
+  /// is never a window where neither the old nor the new manifest is
+  /// complete and durable on disk, so a crash mid-rotation always leaves a
+  /// `CURRENT` pointing at a manifest that fully describes the database.
+  pub(super) fn rotate(&self) -> Result<(), Error<Manifest>> {
+    let mut guard = self.log.lock();
+    let snapshot = guard.snapshot();
+    let new_fid = snapshot.last_fid.next();
+    let new_filename = manifest_filename(new_fid);
+    let new_path = self.dir.join(&new_filename);
+
+    let mut entries = Vec::new();
+    entries.push(Entry::creation(snapshot.metadata_record()));
+    for table in snapshot.tables.values() {
+      if table.is_removed() {
+        continue;
+      }
+      entries.push(Entry::creation(ManifestRecord::table(
+        table.id,
+        table.name.clone(),
+      )));
+      for fid in table.logs.iter() {
+        entries.push(Entry::creation(ManifestRecord::log_with_codec(
+          *fid,
+          table.id,
+          table.codec(*fid),
+        )));
+      }
+      for fid in table.vlogs.iter() {
+        entries.push(Entry::creation_with_custom_flags(
+          CustomFlags::empty().with_bit1(),
+          ManifestRecord::log_with_codec(*fid, table.id, table.codec(*fid)),
+        ));
+      }
+    }
+
+    let mut open_options = OpenOptions::new();
+    open_options.read(true).create_new(true).append(true);
+    let mut new_log = AppendLog::open(
+      &new_path,
+      ManifestOptions::new().with_rewrite_threshold(self.rewrite_threshold),
+      open_options,
+      Options::new().with_magic_version(self.version),
+    )?;
+    new_log.append_batch(entries)?;
+    new_log.flush()?;
+
+    let old_filename = manifest_filename(self.current_fid.load());
+    write_current(&self.dir, &new_filename).map_err(aol::fs::Error::from)?;
+    self.current_fid.store(new_fid);
+
+    *guard = new_log;
+    drop(guard);
+
+    if old_filename != new_filename {
+      let _ = fs::remove_file(self.dir.join(&old_filename));
+    }
+
+    Ok(())
+  }
 }
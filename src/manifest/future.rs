@@ -6,11 +6,16 @@ use futures::lock::Mutex;
 #[cfg(feature = "tokio")]
 use tokio::sync::Mutex;
 
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
 use crate::AsyncMu;
 
 pub(crate) struct AsyncManifestFile {
   kind: Mutex<ManifestFileKind>,
   fid: Fid,
+  sequence: AtomicU64,
+  snapshots: Arc<SnapshotList>,
 }
 
 impl AsyncManifestFile {
@@ -20,26 +25,58 @@ impl AsyncManifestFile {
     opts: ManifestOptions,
   ) -> Result<Self, ManifestFileError> {
     match dir {
-      Some(dir) => disk::DiskManifest::open(dir, opts.rewrite_threshold, opts.version)
-        .map(|file| Self {
+      Some(dir) => {
+        disk::DiskManifest::open(dir, opts.rewrite_threshold, opts.version, opts.recovery_mode)
+          .map(|file| Self {
+            fid: Fid::new(0),
+            sequence: AtomicU64::new(file.sequence()),
+            snapshots: Arc::new(SnapshotList::new()),
+            kind: Mutex::new(ManifestFileKind::Disk(file)),
+          })
+          .map_err(Into::into)
+      }
+      None => {
+        let manifest = memory::MemoryManifest::new(opts);
+        let sequence = AtomicU64::new(manifest.sequence());
+        Ok(Self {
           fid: Fid::new(0),
-          kind: Mutex::new(ManifestFileKind::Disk(file)),
+          sequence,
+          snapshots: Arc::new(SnapshotList::new()),
+          kind: Mutex::new(ManifestFileKind::Memory(manifest)),
         })
-        .map_err(Into::into),
-      None => Ok(Self {
-        fid: Fid::new(0),
-        kind: Mutex::new(ManifestFileKind::Memory(memory::MemoryManifest::new(opts))),
-      }),
+      }
     }
   }
 
   #[cfg(not(feature = "std"))]
   pub(crate) fn open() -> Result<Self, ManifestFileError> {
     Ok(Self {
+      sequence: AtomicU64::new(0),
+      snapshots: Arc::new(SnapshotList::new()),
       kind: Mutex::new(ManifestFileKind::Memory(memory::MemoryManifest::new())),
     })
   }
 
+  /// Allocates and returns the next sequence number, for tagging a newly
+  /// committed write.
+  #[inline]
+  pub(crate) fn next_sequence(&self) -> u64 {
+    self.sequence.fetch_add(1, Ordering::AcqRel) + 1
+  }
+
+  /// Pins the current sequence number so that compaction/GC will not reclaim
+  /// data still visible to it until the returned handle is dropped.
+  #[inline]
+  pub(crate) fn pin_snapshot(&self) -> SnapshotHandle {
+    self.snapshots.pin(self.sequence.load(Ordering::Acquire))
+  }
+
+  /// Returns the oldest sequence number still pinned by a live snapshot.
+  #[inline]
+  pub(crate) fn oldest_snapshot(&self) -> Option<u64> {
+    self.snapshots.oldest()
+  }
+
   #[inline]
   pub(crate) async fn append(&self, ent: Entry<ManifestRecord>) -> Result<(), ManifestFileError> {
     let mut kind = self.kind.lock_me().await;
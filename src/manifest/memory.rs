@@ -14,6 +14,7 @@ impl Snapshot for Manifest {
       tables: HashMap::new(),
       last_fid: Fid::new(0),
       last_table_id: TableId::new(0),
+      sequence: 0,
       creations: 0,
       deletions: 0,
       opts,
@@ -40,14 +41,25 @@ impl Snapshot for Manifest {
   }
 
   fn into_iter(self) -> impl Iterator<Item = Entry<Self::Record>> {
-    self
-      .tables
-      .into_iter()
+    let metadata = core::iter::once(Entry::creation(ManifestRecord::metadata(
+      self.last_fid,
+      self.last_table_id,
+      self.sequence,
+    )));
+
+    metadata.chain(
+      self
+        .tables
+        .into_iter()
       .filter_map(|(tid, table)| {
         if table.is_removed() {
           return None;
         }
 
+        let codecs = std::rc::Rc::new(table.codecs);
+        let vlog_codecs = codecs.clone();
+        let log_codecs = codecs;
+
         Some(
           core::iter::once(Entry::creation(ManifestRecord::Table {
             id: tid,
@@ -58,21 +70,21 @@ impl Snapshot for Manifest {
               .vlogs
               .into_iter()
               .map(move |fid| {
+                let codec = vlog_codecs.get(&fid).copied().unwrap_or_default();
                 Entry::creation_with_custom_flags(
                   CustomFlags::empty().with_bit1(),
-                  ManifestRecord::Log { fid, tid },
+                  ManifestRecord::log_with_codec(fid, tid, codec),
                 )
               })
-              .chain(
-                table
-                  .logs
-                  .into_iter()
-                  .map(move |fid| Entry::creation(ManifestRecord::Log { fid, tid })),
-              ),
+              .chain(table.logs.into_iter().map(move |fid| {
+                let codec = log_codecs.get(&fid).copied().unwrap_or_default();
+                Entry::creation(ManifestRecord::log_with_codec(fid, tid, codec))
+              })),
           ),
         )
       })
-      .flatten()
+        .flatten(),
+    )
   }
 }
 
@@ -105,4 +117,14 @@ impl MemoryManifest {
   pub(super) fn last_fid(&self) -> Fid {
     self.manifest.last_fid
   }
+
+  #[inline]
+  pub(super) fn sequence(&self) -> u64 {
+    self.manifest.sequence
+  }
+
+  #[inline]
+  pub(super) fn manifest(&self) -> &Manifest {
+    &self.manifest
+  }
 }
@@ -15,10 +15,14 @@ pub(crate) struct CreateOptions {
   )]
   fid: Fid,
 
-  /// The maximum size of the log. Default is 2GB.
+  /// The initial size of the log. Default is 2GB.
   ///
-  /// The log is backed by a mmaped file with the given size.
-  /// So this size determines the mmaped file size.
+  /// The log is backed by a growable mmap: this only floors the size of the
+  /// first chunk mapped, not a hard ceiling. Once an entry would overrun the
+  /// chunk it starts in, the log maps another chunk and keeps writing into
+  /// it, so it can grow past this size without remapping or invalidating
+  /// any `&[u8]` slice handed out from an earlier chunk. See `max_size`
+  /// below for the actual ceiling knob.
   #[viewit(
     getter(const, attrs(doc = "Returns the size of the log.")),
     setter(attrs(doc = "Sets the size of the log."))
@@ -34,14 +38,13 @@ pub(crate) struct CreateOptions {
   )]
   lock: bool,
 
-  /// Whether to sync on write. Default is `true`.
-  ///
-  /// If `true`, the log will sync the data to disk on write.
+  /// How aggressively to sync the log to disk after a write. Default is
+  /// [`SyncPolicy::Always`].
   #[viewit(
-    getter(const, attrs(doc = "Returns if we should sync on write.")),
-    setter(attrs(doc = "Sets whether to sync on write."))
+    getter(const, attrs(doc = "Returns the log's sync policy.")),
+    setter(attrs(doc = "Sets the log's sync policy."))
   )]
-  sync_on_write: bool,
+  sync_policy: SyncPolicy,
 
   /// Whether to open in-memory log. Default is `false`.
   ///
@@ -51,6 +54,66 @@ pub(crate) struct CreateOptions {
     setter(attrs(doc = "Sets whether to open in-memory log."))
   )]
   in_memory: bool,
+
+  /// Whether to back an in-memory log with a `memfd_create` file
+  /// descriptor instead of a bare anonymous mapping. Default is `false`.
+  /// Has no effect unless `in_memory` is also `true`; ignored on platforms
+  /// without `memfd_create` (Linux only), where the bare anonymous mapping
+  /// is used instead.
+  ///
+  /// Backing the log with a real (unlinked) fd instead of a plain
+  /// anonymous mapping lets it be grown with `ftruncate` and, once frozen,
+  /// sealed with `fcntl(F_ADD_SEALS)` so the kernel -- not just the `ro`
+  /// flag on this process's handle -- refuses any further write or growth.
+  #[viewit(
+    getter(const, attrs(doc = "Returns if we should back an in-memory log with memfd_create.")),
+    setter(attrs(doc = "Sets whether to back an in-memory log with memfd_create."))
+  )]
+  memfd: bool,
+
+  /// A ceiling on how large the growable mmap (see `size` above) is allowed
+  /// to grow, in bytes. `None` (the default) keeps today's behavior of
+  /// growing a chunk at a time for as long as entries keep arriving.
+  ///
+  /// Once set, a write that would need to grow the log past this ceiling
+  /// fails with `ValueLogError::NotEnoughSpace` instead of mapping another
+  /// chunk, so the caller rolls over to a new log file the same way it
+  /// already does for a fixed-capacity log.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the maximum size the log is allowed to grow to.")),
+    setter(attrs(doc = "Sets the maximum size the log is allowed to grow to."))
+  )]
+  max_size: Option<u64>,
+
+  /// Declares every value [`MmapValueLog::write_uniform`] stores as exactly
+  /// this many bytes, letting it skip the per-entry [`Header`](crate::wal::vlf::Header)
+  /// length framing and [`MmapValueLog::read_uniform`] locate a record by
+  /// `index * (uniform + CHECKSUM_OVERHEAD)` instead of going through a
+  /// [`Pointer`](crate::Pointer)'s stored size. `None` (the default) keeps
+  /// the ordinary variable-length, `Header`-framed layout.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the fixed record size for uniform-layout writes, if set.")),
+    setter(attrs(doc = "Sets the fixed record size for uniform-layout writes."))
+  )]
+  uniform: Option<u64>,
+
+  /// The write buffer size, in bytes. Default is [`DEFAULT_WRITE_BUFFER_SIZE`].
+  ///
+  /// This mirrors [`TableOptions::write_buffer_size`] so value logs and key
+  /// logs can both be configured from the same knob, not just tables. It has
+  /// no effect on a mmap-backed log's write path today: `MmapValueLog`/
+  /// `LogFile::insert` already write straight into mapped pages rather than
+  /// through a file-write syscall, so there is nothing per-record to
+  /// coalesce the way [`BufWriter`](std::io::BufWriter) coalesces LevelDB's
+  /// log-file writes -- the OS page cache already plays that role, and how
+  /// often it's forced to disk is `sync_policy`'s job, not this field's.
+  /// Kept for API symmetry with [`TableOptions`] and for a future
+  /// syscall-per-write backing that would actually need it.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the write buffer size.")),
+    setter(attrs(doc = "Sets the write buffer size."))
+  )]
+  write_buffer_size: usize,
 }
 
 impl CreateOptions {
@@ -61,12 +124,95 @@ impl CreateOptions {
       fid,
       size: 2 * GB as u64,
       lock: true,
-      sync_on_write: true,
+      sync_policy: SyncPolicy::Always,
       in_memory: false,
+      memfd: false,
+      max_size: None,
+      uniform: None,
+      write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
+    }
+  }
+
+  /// Sets the log's sync policy from the old boolean knob: `true` becomes
+  /// [`SyncPolicy::Always`], `false` becomes [`SyncPolicy::Never`].
+  #[inline]
+  #[deprecated(note = "use `with_sync_policy` instead")]
+  pub fn with_sync_on_write(self, sync_on_write: bool) -> Self {
+    self.with_sync_policy(SyncPolicy::from_bool(sync_on_write))
+  }
+}
+
+/// How aggressively a log flushes to disk after a write.
+///
+/// `Always` pays an fsync per write -- simple, but a throughput cliff under
+/// a write-heavy load. `Never` leaves durability entirely to the OS's own
+/// page-cache writeback. `EveryBytes(n)` sits between the two: it
+/// accumulates bytes written since the last sync and flushes once that
+/// counter crosses `n`, then resets it, bounding how much unsynced data a
+/// crash could lose without paying a syscall per record -- the
+/// `bytes_per_sync` idea from raft-engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyncPolicy {
+  /// Never sync explicitly.
+  Never,
+  /// Sync after every write.
+  Always,
+  /// Sync once at least this many bytes have been written since the last sync.
+  EveryBytes(u64),
+}
+
+impl Default for SyncPolicy {
+  #[inline]
+  fn default() -> Self {
+    Self::Always
+  }
+}
+
+impl SyncPolicy {
+  /// Maps the old `sync_on_write: bool` knob onto the equivalent policy.
+  #[inline]
+  pub(crate) const fn from_bool(sync_on_write: bool) -> Self {
+    if sync_on_write {
+      Self::Always
+    } else {
+      Self::Never
     }
   }
 }
 
+/// Controls how opening a value log reacts to a malformed trailing entry.
+///
+/// A crash mid-write leaves a partially written entry at the end of the log.
+/// Mirrors [`ManifestRecoveryMode`]: the default trusts the log's on-disk
+/// length, the tolerant mode walks every entry and truncates back to the
+/// last one that validates instead of surfacing the corruption to the
+/// caller.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueLogRecoveryMode {
+  /// Trust the log's on-disk length; a corrupt trailing entry surfaces as a
+  /// [`ValueLogError::Corrupted`](crate::error::ValueLogError::Corrupted)
+  /// from whichever read reaches it.
+  #[default]
+  Strict,
+  /// Walk the log entry-by-entry on open and rewind back to the last entry
+  /// that validates, discarding a corrupt or torn tail instead of failing.
+  Tolerant,
+}
+
+/// Controls how an in-memory log backs its storage.
+///
+/// Mirrors the choice an in-memory log makes when opened without a
+/// directory on disk: either a heap-allocated map, or a map backed by an
+/// anonymous memory mapping.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MemoryMode {
+  /// Backs the log with a heap-allocated map. The default.
+  #[default]
+  Memory,
+  /// Backs the log with an anonymous memory mapping.
+  MmapAnonymous,
+}
+
 /// The options for opening a log.
 #[viewit::viewit(getters(style = "move"), setters(prefix = "with"))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -86,13 +232,24 @@ pub(crate) struct OpenOptions {
     setter(attrs(doc = "Sets whether to lock the log."))
   )]
   lock: bool,
+
+  /// How to handle a malformed trailing entry on open. Default is `Strict`.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the value log recovery mode.")),
+    setter(attrs(doc = "Sets the value log recovery mode."))
+  )]
+  recovery_mode: ValueLogRecoveryMode,
 }
 
 impl OpenOptions {
   /// Creates a new create options with the default values.
   #[inline]
   pub const fn new(fid: Fid) -> Self {
-    Self { fid, lock: true }
+    Self {
+      fid,
+      lock: true,
+      recovery_mode: ValueLogRecoveryMode::Strict,
+    }
   }
 }
 
@@ -142,6 +299,79 @@ impl ValueLogOptions {
   }
 }
 
+/// The compression codec a value log / frozen log was written with.
+///
+/// Recorded per-log in the manifest (see `ManifestRecord::Log`) so that a
+/// reader can pick the right decompressor without probing the file, and so
+/// the default codec can change over time while old logs stay readable.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum CompressionType {
+  /// No compression.
+  #[default]
+  None = 0,
+  /// LZ4 block compression.
+  Lz4 = 1,
+  /// Zstd compression.
+  Zstd = 2,
+}
+
+impl CompressionType {
+  #[inline]
+  pub(crate) const fn from_u8(v: u8) -> Self {
+    match v {
+      1 => Self::Lz4,
+      2 => Self::Zstd,
+      _ => Self::None,
+    }
+  }
+}
+
+/// A source of the current time, in the same unit
+/// [`Meta::set_expire_at`](crate::types::Meta::set_expire_at) is called
+/// with, used to decide whether a [`Meta::is_expired`](crate::types::Meta::is_expired)
+/// entry should be treated as logically absent.
+///
+/// [`SystemClock`] is the default, real-time implementation; a deterministic
+/// test can supply its own `Clock` instead of depending on wall-clock time.
+pub trait Clock: Send + Sync {
+  /// Returns the current time.
+  fn now(&self) -> u64;
+}
+
+/// A [`Clock`] backed by the system's real-time clock, reporting the
+/// current Unix timestamp in seconds.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+  #[inline]
+  fn now(&self) -> u64 {
+    std::time::SystemTime::now()
+      .duration_since(std::time::UNIX_EPOCH)
+      .map(|d| d.as_secs())
+      .unwrap_or(0)
+  }
+}
+
+/// Controls how `ManifestFile::open` reacts to a malformed trailing record.
+///
+/// A crash during `append`/`append_batch` naturally leaves a partially
+/// written record at the end of the manifest file. Mirrors LevelDB's manifest
+/// recovery, which stops cleanly at a truncated/corrupt tail rather than
+/// failing the whole open.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ManifestRecoveryMode {
+  /// Fail `ManifestFile::open` if the last record cannot be decoded.
+  #[default]
+  Strict,
+  /// Truncate the manifest back to the last successfully decoded record and
+  /// open with the recovered prefix, instead of failing.
+  Tolerant,
+}
+
 /// The options for opening a manifest file.
 #[viewit::viewit(getters(style = "move"), setters(prefix = "with"))]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -161,6 +391,12 @@ pub struct ManifestOptions {
     setter(attrs(doc = "Sets the rewrite threshold for the manifest file."))
   )]
   rewrite_threshold: usize,
+  /// How to handle a torn trailing record on open. Default is `Strict`.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the manifest recovery mode.")),
+    setter(attrs(doc = "Sets the manifest recovery mode."))
+  )]
+  recovery_mode: ManifestRecoveryMode,
 }
 
 impl Default for ManifestOptions {
@@ -177,6 +413,7 @@ impl ManifestOptions {
     Self {
       version: 0,
       rewrite_threshold: 10000,
+      recovery_mode: ManifestRecoveryMode::Strict,
     }
   }
 }
@@ -231,14 +468,13 @@ pub struct WalOptions {
   )]
   lock: bool,
 
-  /// Whether to sync on write. Default is `true`.
-  ///
-  /// If `true`, the log will sync the data to disk on write.
+  /// How aggressively to sync the log to disk after a write. Default is
+  /// [`SyncPolicy::Always`].
   #[viewit(
-    getter(const, attrs(doc = "Returns if we should sync on write.")),
-    setter(attrs(doc = "Sets whether to sync on write."))
+    getter(const, attrs(doc = "Returns the log's sync policy.")),
+    setter(attrs(doc = "Sets the log's sync policy."))
   )]
-  sync_on_write: bool,
+  sync_policy: SyncPolicy,
 
   /// Whether to open in-memory log. Default is `false`.
   ///
@@ -248,6 +484,49 @@ pub struct WalOptions {
     setter(attrs(doc = "Sets whether to open in-memory log."))
   )]
   in_memory: bool,
+
+  /// The minimum value size, in bytes, before a value is considered for
+  /// compression, whether it ends up in the value log or stored inline in
+  /// the active log. Default is 256 bytes.
+  ///
+  /// Values shorter than this are stored verbatim: compressing small values
+  /// rarely pays for the header overhead and the decompression cost on read.
+  #[viewit(
+    getter(
+      const,
+      attrs(doc = "Returns the minimum value size considered for compression.")
+    ),
+    setter(attrs(doc = "Sets the minimum value size considered for compression."))
+  )]
+  min_compress_len: u64,
+
+  /// The compression codec applied to values at or above
+  /// `min_compress_len`, in both the value log and the active log's inline
+  /// values. Default is [`CompressionType::None`], so existing logs stay
+  /// readable unless compression is opted into.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the value log compression codec.")),
+    setter(attrs(doc = "Sets the value log compression codec."))
+  )]
+  compression: CompressionType,
+
+  /// How to handle a malformed trailing entry in the value log on open.
+  /// Default is `Strict`.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the value log recovery mode.")),
+    setter(attrs(doc = "Sets the value log recovery mode."))
+  )]
+  recovery_mode: ValueLogRecoveryMode,
+
+  /// The write buffer size, in bytes. Default is [`DEFAULT_WRITE_BUFFER_SIZE`].
+  ///
+  /// Mirrors [`CreateOptions::write_buffer_size`], which documents why this
+  /// doesn't gate an actual coalescing buffer for a mmap-backed log today.
+  #[viewit(
+    getter(const, attrs(doc = "Returns the write buffer size.")),
+    setter(attrs(doc = "Sets the write buffer size."))
+  )]
+  write_buffer_size: usize,
 }
 
 impl Default for WalOptions {
@@ -267,11 +546,23 @@ impl WalOptions {
       value_threshold: MB as u64,
       big_value_threshold: GB as u64,
       lock: true,
-      sync_on_write: true,
+      sync_policy: SyncPolicy::Always,
       in_memory: false,
+      min_compress_len: 256,
+      compression: CompressionType::None,
+      recovery_mode: ValueLogRecoveryMode::Strict,
+      write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
     }
   }
 
+  /// Sets the log's sync policy from the old boolean knob: `true` becomes
+  /// [`SyncPolicy::Always`], `false` becomes [`SyncPolicy::Never`].
+  #[inline]
+  #[deprecated(note = "use `with_sync_policy` instead")]
+  pub fn with_sync_on_write(self, sync_on_write: bool) -> Self {
+    self.with_sync_policy(SyncPolicy::from_bool(sync_on_write))
+  }
+
   /// Creates a new log manager options with the given log size.
   #[inline]
   pub(crate) const fn create_options(&self, fid: Fid) -> CreateOptions {
@@ -279,8 +570,12 @@ impl WalOptions {
       fid,
       size: self.log_size,
       lock: self.lock,
-      sync_on_write: self.sync_on_write,
+      sync_policy: self.sync_policy,
       in_memory: self.in_memory,
+      memfd: false,
+      max_size: None,
+      uniform: None,
+      write_buffer_size: self.write_buffer_size,
     }
   }
 
@@ -290,6 +585,7 @@ impl WalOptions {
     OpenOptions {
       fid,
       lock: self.lock,
+      recovery_mode: self.recovery_mode,
     }
   }
 }
@@ -381,6 +677,8 @@ pub struct TableOptions {
   /// The write buffer size. Default is `1024`.
   ///
   /// The write buffer is used to buffer the write operations before they are written to the database.
+  /// See [`CreateOptions::write_buffer_size`] for why this doesn't gate an
+  /// actual coalescing buffer for a mmap-backed log today.
   #[viewit(
     getter(const, attrs(doc = "Returns the write buffer size.")),
     setter(attrs(doc = "Sets the write buffer size."))
@@ -396,14 +694,41 @@ pub struct TableOptions {
   )]
   lock: bool,
 
-  /// Whether to sync on write. Default is `true`.
-  ///
-  /// If `true`, the log will sync the data to disk on write.
+  /// How aggressively to sync the log to disk after a write. Default is
+  /// [`SyncPolicy::Always`]. See [`WalOptions::sync_policy`].
+  #[viewit(
+    getter(const, attrs(doc = "Returns the log's sync policy.")),
+    setter(attrs(doc = "Sets the log's sync policy."))
+  )]
+  sync_policy: SyncPolicy,
+
+  /// The minimum value size, in bytes, before a value is considered for
+  /// compression. Default is 256 bytes. See [`WalOptions::min_compress_len`].
+  #[viewit(
+    getter(
+      const,
+      attrs(doc = "Returns the minimum value size considered for compression.")
+    ),
+    setter(attrs(doc = "Sets the minimum value size considered for compression."))
+  )]
+  min_compress_len: u64,
+
+  /// The compression codec applied to values at or above
+  /// `min_compress_len`. Default is [`CompressionType::None`].
+  /// See [`WalOptions::compression`].
   #[viewit(
-    getter(const, attrs(doc = "Returns if we should sync on write.")),
-    setter(attrs(doc = "Sets whether to sync on write."))
+    getter(const, attrs(doc = "Returns the value log compression codec.")),
+    setter(attrs(doc = "Sets the value log compression codec."))
   )]
-  sync_on_write: bool,
+  compression: CompressionType,
+
+  /// How to handle a malformed trailing entry in the table's log on open.
+  /// Default is `Strict`. See [`WalOptions::recovery_mode`].
+  #[viewit(
+    getter(const, attrs(doc = "Returns the value log recovery mode.")),
+    setter(attrs(doc = "Sets the value log recovery mode."))
+  )]
+  recovery_mode: ValueLogRecoveryMode,
 }
 
 impl Default for TableOptions {
@@ -423,16 +748,28 @@ impl TableOptions {
       create: false,
       create_new: false,
       standalone: false,
-      write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
+      write_buffer_size: wal.write_buffer_size,
       log_size: wal.log_size,
       vlog_size: wal.vlog_size,
       value_threshold: wal.value_threshold,
       big_value_threshold: wal.big_value_threshold,
-      sync_on_write: wal.sync_on_write,
+      sync_policy: wal.sync_policy,
       lock: wal.lock,
+      min_compress_len: wal.min_compress_len,
+      compression: wal.compression,
+      recovery_mode: wal.recovery_mode,
+      write_buffer_size: wal.write_buffer_size,
     }
   }
 
+  /// Sets the log's sync policy from the old boolean knob: `true` becomes
+  /// [`SyncPolicy::Always`], `false` becomes [`SyncPolicy::Never`].
+  #[inline]
+  #[deprecated(note = "use `with_sync_policy` instead")]
+  pub fn with_sync_on_write(self, sync_on_write: bool) -> Self {
+    self.with_sync_policy(SyncPolicy::from_bool(sync_on_write))
+  }
+
   #[inline]
   pub(crate) fn to_wal_options(&self, in_memory: bool) -> WalOptions {
     WalOptions {
@@ -440,9 +777,13 @@ impl TableOptions {
       vlog_size: self.vlog_size,
       value_threshold: self.value_threshold,
       big_value_threshold: self.big_value_threshold,
-      sync_on_write: self.sync_on_write,
+      sync_policy: self.sync_policy,
       in_memory,
       lock: self.lock,
+      min_compress_len: self.min_compress_len,
+      compression: self.compression,
+      recovery_mode: self.recovery_mode,
+      write_buffer_size: self.write_buffer_size,
     }
   }
 }
@@ -454,13 +795,16 @@ impl From<WalOptions> for TableOptions {
       create: false,
       create_new: false,
       standalone: false,
-      write_buffer_size: DEFAULT_WRITE_BUFFER_SIZE,
+      write_buffer_size: val.write_buffer_size,
       log_size: val.log_size,
       vlog_size: val.vlog_size,
       value_threshold: val.value_threshold,
       big_value_threshold: val.big_value_threshold,
-      sync_on_write: val.sync_on_write,
+      sync_policy: val.sync_policy,
       lock: val.lock,
+      min_compress_len: val.min_compress_len,
+      compression: val.compression,
+      recovery_mode: val.recovery_mode,
     }
   }
 }
@@ -515,14 +859,13 @@ pub struct Options {
   )]
   lock: bool,
 
-  /// Whether to sync on write. Default is `true`.
-  ///
-  /// If `true`, the log will sync the data to disk on write.
+  /// How aggressively to sync the log to disk after a write. Default is
+  /// [`SyncPolicy::Always`].
   #[viewit(
-    getter(const, attrs(doc = "Returns if we should sync on write.")),
-    setter(attrs(doc = "Sets whether to sync on write."))
+    getter(const, attrs(doc = "Returns the log's sync policy.")),
+    setter(attrs(doc = "Sets the log's sync policy."))
   )]
-  sync_on_write: bool,
+  sync_policy: SyncPolicy,
 
   /// Whether to open in-memory log. Default is `false`.
   ///
@@ -532,6 +875,25 @@ pub struct Options {
     setter(attrs(doc = "Sets whether to open in-memory log."))
   )]
   in_memory: bool,
+
+  /// Whether to open the database as read-only. Default is `false`.
+  ///
+  /// If `true`, [`Db::open_table`](crate::sync::Db::open_table) will refuse
+  /// to create a table that does not already exist, and recovery will never
+  /// write back to the manifest.
+  #[viewit(
+    getter(const, attrs(doc = "Returns if the database is read-only.")),
+    setter(attrs(doc = "Sets whether the database is read-only."))
+  )]
+  read_only: bool,
+
+  /// How to handle a malformed trailing entry in a table's log on open.
+  /// Default is `Strict`. See [`ValueLogRecoveryMode`].
+  #[viewit(
+    getter(const, attrs(doc = "Returns the recovery mode used when opening a table's logs.")),
+    setter(attrs(doc = "Sets the recovery mode used when opening a table's logs."))
+  )]
+  recovery_mode: ValueLogRecoveryMode,
 }
 
 impl Default for Options {
@@ -551,20 +913,55 @@ impl Options {
       value_threshold: MB as u64,
       big_value_threshold: GB as u64,
       lock: true,
-      sync_on_write: true,
+      sync_policy: SyncPolicy::Always,
       in_memory: false,
+      read_only: false,
+      recovery_mode: ValueLogRecoveryMode::Strict,
     }
   }
 
+  /// Sets the log's sync policy from the old boolean knob: `true` becomes
+  /// [`SyncPolicy::Always`], `false` becomes [`SyncPolicy::Never`].
+  #[inline]
+  #[deprecated(note = "use `with_sync_policy` instead")]
+  pub fn with_sync_on_write(self, sync_on_write: bool) -> Self {
+    self.with_sync_policy(SyncPolicy::from_bool(sync_on_write))
+  }
+
   /// Creates a new log manager options with the given log size.
   #[inline]
   pub(crate) const fn create_options(&self, fid: Fid) -> CreateOptions {
+    let defaults = WalOptions::new();
     CreateOptions {
       fid,
       size: self.log_size,
       lock: self.lock,
-      sync_on_write: self.sync_on_write,
+      sync_policy: self.sync_policy,
       in_memory: self.in_memory,
+      memfd: false,
+      max_size: None,
+      uniform: None,
+      write_buffer_size: defaults.write_buffer_size,
+    }
+  }
+
+  /// Converts these database-wide options into the [`WalOptions`] a
+  /// specific table's [`Wal`](crate::wal::Wal) is opened/created with.
+  #[inline]
+  pub(crate) const fn to_wal_options(&self, in_memory: bool) -> WalOptions {
+    let defaults = WalOptions::new();
+    WalOptions {
+      log_size: self.log_size,
+      vlog_size: self.vlog_size,
+      value_threshold: self.value_threshold,
+      big_value_threshold: self.big_value_threshold,
+      sync_policy: self.sync_policy,
+      in_memory,
+      lock: self.lock,
+      min_compress_len: defaults.min_compress_len,
+      compression: defaults.compression,
+      recovery_mode: self.recovery_mode,
+      write_buffer_size: defaults.write_buffer_size,
     }
   }
 }
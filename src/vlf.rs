@@ -80,6 +80,10 @@ impl Header {
     let (kp_size, kp) = Pointer::decode(&buf[readed..]).map_err(|e| match e {
       PointerError::VarintError(e) => DecodeHeaderError::VarintError(e),
       PointerError::NotEnoughBytes => DecodeHeaderError::NotEnoughBytes,
+      // both indicate a corrupt pointer that failed to decode cleanly
+      PointerError::Decode(_) | PointerError::SizeMismatch { .. } => {
+        DecodeHeaderError::NotEnoughBytes
+      }
       PointerError::BufferTooSmall => unreachable!(),
     })?;
     readed += kp_size;
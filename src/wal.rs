@@ -9,7 +9,6 @@ use manifest::{ManifestFile, ManifestRecord};
 use quick_cache::sync::Cache;
 use skl::{Ascend, Trailer};
 
-#[cfg(feature = "std")]
 use vlf::ValueLog;
 
 use crate::options::CreateOptions;
@@ -23,13 +22,12 @@ use super::{
 };
 
 mod lf;
-#[cfg(feature = "std")]
 mod vlf;
 
 #[cfg(feature = "sync")]
 mod sync;
 #[cfg(feature = "sync")]
-pub(crate) use sync::Wal;
+pub(crate) use sync::{Wal, WalStats};
 
 #[cfg(feature = "future")]
 mod future;
@@ -3,12 +3,15 @@ use super::{options::*, *};
 use core::{
   cell::RefCell,
   ops::{Bound, RangeBounds},
+  sync::atomic::{AtomicU64, Ordering},
 };
 use std::io;
 
 use bytes::Bytes;
 use skl::SkipMap;
 
+use crate::options::ValueLogRecoveryMode;
+
 pub use skl::{Ascend, Comparator, Descend, OccupiedValue};
 
 use either::Either;
@@ -16,6 +19,42 @@ use either::Either;
 mod iterator;
 pub use iterator::*;
 
+// NOTE: there is no `MergingIterator` over several `LogFile`s yet. The
+// design is a straightforward k-way merge on top of what `LogFileIterator`
+// already exposes: wrap one `LogFileIterator` per source `LogFile` (the
+// immutable ones plus the active one) in a small `Source { iter, peeked:
+// Option<EntryRef<'a, C>> }`, push each into a `BinaryHeap<Reverse<...>>`
+// keyed by `(comparator-ordered key, Reverse(version))` so the smallest key
+// -- and, among ties, the highest version -- sorts first, then `next()` pops
+// the head, advances just that source, and skips forward past every other
+// heap entry sharing the same key (those are older versions of a key the
+// popped entry already shadows) before re-pushing them. `DoubleEndedIterator`
+// is the mirror using `next_back`/a max-heap. `seek_lower_bound`/
+// `seek_upper_bound` over the merged view reduce to calling each source's
+// own `LogFileIterator::seek_lower_bound`/`seek_upper_bound` and re-seeding
+// the heap from whatever each returns, which is exactly why each source
+// needs to stay a `LogFileIterator` rather than a flattened `Vec<EntryRef>`.
+// The `minimum`/`maximum` fast-path this request asks for is just a filter
+// applied before a `LogFile` is wrapped as a source at all: compare the
+// query range against the cached `LogFile::minimum`/`maximum` (already used
+// by `LogFile::contains_key`) and never construct a `LogFileIterator` for a
+// file the range can't intersect, so an out-of-range file is never polled
+// even once.
+//
+// None of this can be added today, though: every step above returns or
+// stores `EntryRef<'a, C>` from `LogFileIterator`, and that type is defined
+// in `super::lf::iterator` -- a module this file declares (`mod iterator;`)
+// but has no backing `wal/lf/iterator.rs` for, so `LogFile::get` and every
+// other `EntryRef`-returning signature in this file already fail to resolve
+// before a merging iterator would even get a chance to use them. (The
+// orphaned `crate::lf::iterator` module has a same-named, structurally
+// similar `LogFileIterator` -- including `seek_lower_bound`/
+// `seek_upper_bound` -- but it yields the single-generic
+// `crate::types::EntryRef<'a>`, not the two-parameter `EntryRef<'a, C>`
+// this file's `LogFile<C>` needs, so it isn't a drop-in replacement either.)
+// A merging iterator belongs on top of a compiling `LogFileIterator`, not
+// invented as a parallel type that papers over the gap.
+
 const EXTENSION: &str = "klog";
 
 std::thread_local! {
@@ -67,11 +106,68 @@ impl core::fmt::Display for Error {
   }
 }
 
+// NOTE: unlike the `.vlog` files `MmapValueLog` owns outright (see the
+// magic/version/fid header `MmapValueLog::create`/`open` now carry), a
+// `.klog` file's on-disk layout is entirely `skl::SkipMap`'s: `create`/`open`
+// below hand the whole file straight to `SkipMap::mmap_mut_with_comparator`/
+// `mmap_with_comparator`, which impose their own header at offset 0. Writing
+// a second, crate-owned magic/version/fid header in front of that would mean
+// either shifting every byte `SkipMap` itself writes (not possible without
+// forking it) or layering a second file-within-a-file that `SkipMap` knows
+// nothing about and would refuse to open. A self-describing `.klog` would
+// have to come from `SkipMap` itself growing that feature, or from this
+// crate no longer delegating the file format to it -- neither is a change
+// to make incidentally under this request.
+//
+// NOTE: this also means a `.klog`'s arena can't get the reserve-and-grow
+// treatment `MmapValueLog` gives `.vlog` files (see its `CHUNK_SIZE`
+// chunking): `create`/`open` below hand `opts.size` straight to
+// `SkipMap::with_comparator`/`mmap_mut_with_comparator`, which commit the
+// whole arena up front with no grow-on-demand entry point this crate can
+// hook into. A table opened in `standalone` mode still pays `log_size`
+// (2GB by default) per table for this reason; shrinking that cost needs
+// `SkipMap` itself to expose a growable arena, not a change here.
+//
+// NOTE: a `reserve_address_space` knob on `CreateOptions`/`WalOptions` that
+// maps a large `PROT_NONE`/reservation-only region up front and commits
+// pages into it as `self.map.insert` reports `InsufficientSpace`, retrying
+// the same insert in place before rotating to a new `LogFile`, would need
+// that same grow-on-demand entry point into `SkipMap`'s arena -- the one
+// the paragraph above already says doesn't exist. `SkipMap::with_comparator`/
+// `mmap_mut_with_comparator` take one fixed `opts.size` and hand back an
+// arena sized to exactly that; there is no `SkipMap::grow`/remap call this
+// crate could retry into on `InsufficientSpace` instead of minting the new
+// `LogFile` `insert_to_log` already falls back to in `wal/sync.rs`. The
+// retry-before-rotate behavior this request wants is a `SkipMap` feature,
+// not something `LogFile::create`/`open` can add by reading one more field
+// off `CreateOptions`.
+//
+// NOTE: advisory locking over a second process (or handle) mapping the
+// same `.klog` is, once again, already `SkipMap`'s call rather than ours:
+// `create`/`open` below pass `opts.lock` straight into
+// `SkipMap::mmap_mut_with_comparator`/`mmap_with_comparator`, which open
+// the file and flock it internally before handing back the arena --
+// `LogFile` never holds a raw `std::fs::File` of its own to layer a second,
+// crate-owned `fs4`/`fs2` lock on top of (the way `MmapValueLog` does for
+// `.vlog` files, see its `fs4::FileExt` use). The `CreateOptions`/
+// `OpenOptions::lock` toggle this request asks for already exists and
+// already defaults to `true`; what is missing is a distinct
+// `LogFileError::Locked` a caller could match on, and a shared-vs-exclusive
+// split for read-only opens, and neither is reachable here: a failed lock
+// surfaces as an opaque `LogFileError::Log(skl::map::Error)` today, and
+// this crate has no visibility into that error's variants to pick a
+// locked-specific one out of it, let alone a way to ask `SkipMap` for a
+// shared rather than exclusive lock. Both would need `SkipMap` itself to
+// grow a richer locking API.
 /// A append-only log based on on-disk [`SkipMap`] for key-value databases based on bitcask model.
 pub struct LogFile<C = Ascend> {
   map: SkipMap<Meta, C>,
   fid: u32,
-  sync_on_write: bool,
+  sync_policy: SyncPolicy,
+  /// Bytes written since the last flush, only meaningful under
+  /// [`SyncPolicy::EveryBytes`]; reset to `0` every time that threshold is
+  /// crossed and a flush actually happens.
+  unsynced_bytes: AtomicU64,
   ro: bool,
   minimum: Option<Bytes>,
   maximum: Option<Bytes>,
@@ -130,7 +226,8 @@ impl<C: Comparator> LogFile<C> {
         .map(|map| Self {
           map,
           fid: opts.fid,
-          sync_on_write: opts.sync_on_write,
+          sync_policy: opts.sync_policy,
+          unsynced_bytes: AtomicU64::new(0),
           ro: false,
           minimum: None,
           maximum: None,
@@ -146,7 +243,8 @@ impl<C: Comparator> LogFile<C> {
         .map(|map| Self {
           map,
           fid: opts.fid,
-          sync_on_write: opts.sync_on_write,
+          sync_policy: opts.sync_policy,
+          unsynced_bytes: AtomicU64::new(0),
           ro: false,
           minimum: None,
           maximum: None,
@@ -161,7 +259,8 @@ impl<C: Comparator> LogFile<C> {
       .map(|map| Self {
         map,
         fid: opts.fid,
-        sync_on_write: opts.sync_on_write,
+        sync_policy: opts.sync_policy,
+        unsynced_bytes: AtomicU64::new(0),
         ro: false,
         minimum: None,
         maximum: None,
@@ -172,35 +271,113 @@ impl<C: Comparator> LogFile<C> {
   /// Open an existing log with the given options.
   ///
   /// **Note**: `LogFile` constructed with this method is read only.
+  ///
+  /// If `opts.recovery_mode()` is [`ValueLogRecoveryMode::Tolerant`] and a
+  /// crash left a torn trailing record, the file is repeatedly truncated
+  /// one byte shorter and reopened until a valid prefix is found, mirroring
+  /// [`DiskManifest`](crate::manifest)'s torn-tail recovery instead of
+  /// failing the whole open.
   #[cfg(feature = "std")]
   #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-  pub fn open(cmp: C, opts: OpenOptions) -> io::Result<Self> {
+  pub fn open(cmp: C, opts: OpenOptions) -> io::Result<Self>
+  where
+    C: Clone,
+  {
     use std::fmt::Write;
 
     BUF.with(|buf| {
       let mut buf = buf.borrow_mut();
       buf.clear();
       write!(buf, "{:05}.{}", opts.fid, EXTENSION).unwrap();
-      SkipMap::<Meta, C>::mmap_with_comparator(buf.as_str(), opts.lock, cmp).map(|map| {
-        let max_version = map.max_version();
-        let minimum = map.first(max_version).map(|ent| {
-          Bytes::copy_from_slice(ent.key())
-        });
-        let maximum = map.last(max_version).map(|ent| {
-          Bytes::copy_from_slice(ent.key())
-        });
-        Self {
-          map,
-          fid: opts.fid,
-          sync_on_write: false,
-          ro: true,
-          minimum,
-          maximum,
+      let path = buf.as_str();
+
+      match SkipMap::<Meta, C>::mmap_with_comparator(path, opts.lock, cmp.clone()) {
+        Ok(map) => Ok(Self::from_map(opts.fid, map)),
+        Err(e) if opts.recovery_mode == ValueLogRecoveryMode::Tolerant => {
+          Self::recover_torn_tail(path, opts.fid, opts.lock, cmp, e)
         }
-      })
+        Err(e) => Err(e),
+      }
     })
   }
 
+  #[cfg(feature = "std")]
+  fn from_map(fid: Fid, map: SkipMap<Meta, C>) -> Self {
+    let max_version = map.max_version();
+    let minimum = map
+      .first(max_version)
+      .map(|ent| Bytes::copy_from_slice(ent.key()));
+    let maximum = map
+      .last(max_version)
+      .map(|ent| Bytes::copy_from_slice(ent.key()));
+    Self {
+      map,
+      fid,
+      sync_policy: SyncPolicy::Never,
+      unsynced_bytes: AtomicU64::new(0),
+      ro: true,
+      minimum,
+      maximum,
+    }
+  }
+
+  /// Recovers from a torn trailing record by repeatedly truncating the file
+  /// one byte shorter and retrying the open, stopping at the first prefix
+  /// that mmaps cleanly. Returns the caller's original error if no valid
+  /// prefix is found.
+  #[cfg(feature = "std")]
+  fn recover_torn_tail(
+    path: &str,
+    fid: Fid,
+    lock: bool,
+    cmp: C,
+    original_err: io::Error,
+  ) -> io::Result<Self> {
+    let len = std::fs::metadata(path)?.len();
+    let mut candidate = len;
+
+    while candidate > 0 {
+      candidate -= 1;
+      let file = std::fs::OpenOptions::new().write(true).open(path)?;
+      file.set_len(candidate)?;
+      drop(file);
+
+      if let Ok(map) = SkipMap::<Meta, C>::mmap_with_comparator(path, lock, cmp.clone()) {
+        tracing::warn!(
+          path,
+          offset = candidate,
+          "log torn trailing record truncated during recovery"
+        );
+        return Ok(Self::from_map(fid, map));
+      }
+    }
+
+    Err(original_err)
+  }
+
+  /// Decides whether a write of `written` bytes should flush now, per
+  /// `self.sync_policy`: always under [`SyncPolicy::Always`], never under
+  /// [`SyncPolicy::Never`], and under [`SyncPolicy::EveryBytes`] only once
+  /// the bytes accumulated since the last flush cross the threshold --
+  /// resetting the counter when it does, so it counts from zero again
+  /// rather than drifting ever further past the threshold.
+  #[inline]
+  fn should_sync(&self, written: u64) -> bool {
+    match self.sync_policy {
+      SyncPolicy::Never => false,
+      SyncPolicy::Always => true,
+      SyncPolicy::EveryBytes(threshold) => {
+        let total = self.unsynced_bytes.fetch_add(written, Ordering::Relaxed) + written;
+        if total >= threshold {
+          self.unsynced_bytes.store(0, Ordering::Relaxed);
+          true
+        } else {
+          false
+        }
+      }
+    }
+  }
+
   /// Inserts the given key and value to the log.
   #[inline]
   pub fn insert<'a, 'b: 'a>(
@@ -209,9 +386,10 @@ impl<C: Comparator> LogFile<C> {
     key: &'b [u8],
     value: &'b [u8],
   ) -> Result<Option<EntryRef<'a, C>>, Error> {
+    let written = (key.len() + value.len()) as u64;
     match self.map.insert(meta, key, value) {
       Ok(ent) => {
-        if self.sync_on_write {
+        if self.should_sync(written) {
           self.flush()?;
         }
         Ok(ent.map(EntryRef::new))
@@ -235,9 +413,10 @@ impl<C: Comparator> LogFile<C> {
     value_size: u32,
     f: impl FnOnce(OccupiedValue<'a>) -> Result<(), E>,
   ) -> Result<Option<EntryRef<'a, C>>, Either<E, Error>> {
+    let written = (key.len() + value_size as usize) as u64;
     match self.map.insert_with(meta, key, value_size, f) {
       Ok(ent) => {
-        if self.sync_on_write {
+        if self.should_sync(written) {
           self.flush().map_err(|e| Either::Right(e.into()))?;
         }
         Ok(ent.map(EntryRef::new))
@@ -253,14 +432,16 @@ impl<C: Comparator> LogFile<C> {
   /// some of the key-value pairs may be written to the log.
   #[inline]
   pub fn insert_many(&self, batch: &[Entry]) -> Result<(), Error> {
+    let mut written = 0u64;
     for (idx, ent) in batch.iter().enumerate() {
       self
         .map
         .insert(ent.meta(), ent.key(), ent.value())
         .map_err(|e| Error::WriteBatch { idx, source: e })?;
+      written += (ent.key().len() + ent.value().len()) as u64;
     }
 
-    if self.sync_on_write {
+    if self.should_sync(written) {
       self.flush()?;
     }
 
@@ -8,6 +8,34 @@ use manifest::TableManifest;
 #[cfg(feature = "parking_lot")]
 use parking_lot::Mutex;
 
+use super::lf::{EntryRef, LogFileIterator};
+
+/// A point-in-time snapshot of a single table's WAL footprint, cheap to
+/// clone and poll repeatedly. Modeled on OpenEthereum's `ClientReport`:
+/// plain counters and byte-sizes, no owned collections.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct WalStats {
+  /// Number of live (non-tombstone) keys in the active log segment.
+  ///
+  /// Scoped to the active segment only, the same segment [`Wal::iter`]
+  /// treats as authoritative -- merging in older, already-rotated-out
+  /// segments needs a cross-segment iterator this tree doesn't have yet.
+  pub(crate) live_keys: u64,
+  /// Number of tombstones (deleted keys not yet reclaimed) in the active
+  /// log segment.
+  pub(crate) tombstones: u64,
+  /// Number of key-log segments open for this table.
+  pub(crate) log_file_count: usize,
+  /// Bytes the key-log segments' backing maps are currently using.
+  pub(crate) log_bytes: usize,
+  /// Bytes the key-log segments' backing maps have reserved.
+  pub(crate) log_capacity: usize,
+  /// Bytes the active value log has written so far.
+  pub(crate) vlog_bytes: u64,
+  /// Bytes the active value log has reserved.
+  pub(crate) vlog_capacity: u64,
+}
+
 pub(crate) struct Wal<C = Ascend> {
   fid_generator: Arc<AtomicFid>,
 
@@ -43,7 +71,7 @@ impl<C: Comparator + Send + Sync + 'static> Wal<C> {
         cmp.clone(),
         CreateOptions::new(fid)
           .with_size(opts.log_size)
-          .with_sync_on_write(opts.sync_on_write),
+          .with_sync_policy(opts.sync_policy),
       )?,
     );
 
@@ -118,12 +146,167 @@ impl<C: Comparator + Send + Sync + 'static> Wal<C> {
     }
 
     let mut meta = Meta::new(version);
+
+    // Values too small to route to the value log are stored inline in the
+    // active log; compress them in place too, rather than leaving
+    // compression a value-log-only benefit.
+    if let Some(compressed) = crate::types::compress_value_inline(
+      val,
+      self.opts.min_compress_len(),
+      self.opts.compression(),
+    ) {
+      meta.set_compressed();
+      let cks = checksum(meta.raw(), key, Some(&compressed));
+      meta.set_checksum(cks);
+      return self.insert_to_log(tid, meta, key, &compressed);
+    }
+
     let cks = checksum(meta.raw(), key, Some(val));
     meta.set_checksum(cks);
 
     self.insert_to_log(tid, meta, key, val)
   }
 
+  /// Applies a batch of puts and deletes, one after another, without
+  /// releasing the writer's exclusive access to the WAL in between — a
+  /// concurrent reader never observes a partially-applied batch as
+  /// distinct versions landing one at a time. As with
+  /// [`LogFile::insert_many`](crate::wal::lf::LogFile::insert_many), a
+  /// failure partway through still leaves the already-applied entries on
+  /// disk; this is the same best-effort guarantee the underlying log file
+  /// already offers for a single multi-entry write.
+  pub(crate) fn insert_batch(
+    &mut self,
+    tid: TableId,
+    version: u64,
+    batch: &[(Bytes, Option<Bytes>)],
+  ) -> Result<(), Error> {
+    for (key, value) in batch {
+      match value {
+        Some(value) => self.insert(tid, version, key, value)?,
+        None => self.remove(tid, version, key)?,
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Compares two keys using this WAL's [`Comparator`].
+  #[inline]
+  pub(crate) fn compare(&self, a: &[u8], b: &[u8]) -> core::cmp::Ordering {
+    self.cmp.compare(a, b)
+  }
+
+  /// Returns an iterator over the latest version (`<= version`) of every
+  /// entry in comparator order, skipping tombstones.
+  ///
+  /// Scoped to the newest (active) log segment, the same segment
+  /// [`insert_to_log`](Self::insert_to_log) treats as authoritative via
+  /// `self.lfs.back()`; merging in the older, already-rotated-out segments
+  /// held in `lfs` needs a cross-segment iterator this tree doesn't have
+  /// yet.
+  ///
+  /// NOTE: that cross-segment iterator (call it `MergedIterator`) would be a
+  /// `BinaryHeap` of one `LogFileIterator` per entry in `self.lfs`, popped in
+  /// (user key ascending, version descending) order the same way the
+  /// `MergingIterator` NOTE above `LogFile` in `wal/lf.rs` already designs,
+  /// plus lazily resolving a popped value-pointer `Meta` through `vcache`
+  /// only once a caller actually asks for the value rather than eagerly on
+  /// every `next()`. It hits the exact same wall that NOTE describes:
+  /// `LogFileIterator` yields `EntryRef<'a, C>`, and that type's home module
+  /// (`wal/lf::iterator`, declared via `mod iterator;`) has no backing
+  /// `wal/lf/iterator.rs` in this tree, so today's single-segment `iter`
+  /// above doesn't even resolve on its own, let alone a merge over several.
+  /// `Wal::iter(read_version)` belongs on top of a compiling
+  /// `LogFileIterator`, not a hand-rolled replacement for it.
+  #[inline]
+  pub(crate) fn iter(&self, version: u64) -> LogFileIterator<'_, C> {
+    self.lfs.back().expect("no active log file").value().iter(version)
+  }
+
+  /// Returns the entry with the smallest key visible at `version`, or
+  /// `None` if the active log segment is empty.
+  #[inline]
+  pub(crate) fn first(&self, version: u64) -> Option<EntryRef<'_, C>> {
+    self.lfs.back().expect("no active log file").value().first(version)
+  }
+
+  /// Returns the entry with the largest key visible at `version`, or
+  /// `None` if the active log segment is empty.
+  #[inline]
+  pub(crate) fn last(&self, version: u64) -> Option<EntryRef<'_, C>> {
+    self.lfs.back().expect("no active log file").value().last(version)
+  }
+
+  /// Reports this table's current WAL/vlog footprint: segment counts,
+  /// byte-sizes, and live/tombstone key counts in the active segment.
+  ///
+  /// The key counts are derived by diffing [`LogFile::iter`] (live entries
+  /// only) against [`LogFile::iter_all_versions`] rather than inspecting
+  /// each entry's trailer directly, since only the active segment's size
+  /// is tracked incrementally today.
+  pub(crate) fn stats(&self) -> WalStats {
+    let mut log_bytes = 0usize;
+    let mut log_capacity = 0usize;
+    for entry in self.lfs.iter() {
+      let lf = entry.value();
+      log_bytes += lf.size();
+      log_capacity += lf.capacity();
+    }
+
+    let (live_keys, tombstones) = match self.lfs.back() {
+      Some(active) => {
+        let lf = active.value();
+        let live = lf.iter(u64::MAX).count() as u64;
+        let total = lf.iter_all_versions(u64::MAX).count() as u64;
+        (live, total.saturating_sub(live))
+      }
+      None => (0, 0),
+    };
+
+    WalStats {
+      live_keys,
+      tombstones,
+      log_file_count: self.lfs.len(),
+      log_bytes,
+      log_capacity,
+      vlog_bytes: self.vlf.len() as u64,
+      vlog_capacity: self.vlf.capacity(),
+    }
+  }
+
+  // NOTE: no `gc_value_log(fid)` here yet to drive the WiscKey-style
+  // reclaim pass the NOTE above `ValueLog` in `wal/vlf.rs` already designs:
+  // scan a candidate vlog's entries, re-write whichever ones `LogFile::get`
+  // says are still live into the active vlog, append a manifest deletion
+  // once nothing references the old file, and `ValueLog::remove()` it. That
+  // NOTE's blocker applies here verbatim -- `LogFile::get`'s `EntryRef<'a, C>`
+  // return type doesn't resolve because `wal/lf.rs`'s `mod iterator;` has no
+  // backing file -- so `gc_value_log` has no live-lookup to call into yet.
+  // `WalStats` above already tracks `vlog_bytes`/`vlog_capacity` as the
+  // coarse signal a real candidate-selection policy (lowest live ratio)
+  // would read from; it just has nothing to act on below.
+
+  // NOTE: there is no `compact()` here reclaiming the `LogFile`s `insert_to_log`
+  // mints below, so `self.lfs` only ever grows. A size-tiered/leveled design
+  // is the right shape for it: level 0 holds the freshly-rotated files this
+  // method creates (they can overlap in key range), levels >=1 hold
+  // non-overlapping runs, and a compaction picks a level-N file plus every
+  // overlapping level-N+1 file (via `LogFile::minimum`/`maximum` against
+  // `ComparatorWrapper::compare`) and k-way merges them, keeping the newest
+  // version at or below each live snapshot and dropping a key whose surviving
+  // version is a tombstone no snapshot still needs.
+  //
+  // That merge is exactly the blocked `MergingIterator` described in the NOTE
+  // above `LogFile` in `wal/lf.rs`: picking a compaction's survivors means
+  // iterating several `LogFile`s' entries in merged (key, Reverse(version))
+  // order, which needs a compiling `LogFileIterator`/`EntryRef<'a, C>` to
+  // build on. `wal/lf.rs`'s `mod iterator;` has no backing file, so
+  // `LogFile::get` and friends don't resolve yet -- a compactor has nothing
+  // to iterate with until that's filled in. Writing survivors into new
+  // `LogFile`s and swapping them into `self.lfs` under an `aol::Entry`
+  // creation/deletion pair is otherwise a straightforward extension of what
+  // `insert_to_log` already does below; it's the merge step that's blocked.
   #[inline]
   fn insert_to_log(
     &mut self,
@@ -165,7 +348,14 @@ impl<C: Comparator + Send + Sync + 'static> Wal<C> {
 
     let mut buf = [0; Pointer::MAX_ENCODING_SIZE];
     let woffset = self.vlf.len();
-    match self.vlf.write(meta.version(), key, val, meta.checksum()) {
+    match self.vlf.write(
+      meta.version(),
+      key,
+      val,
+      meta.checksum(),
+      self.opts.min_compress_len(),
+      self.opts.compression(),
+    ) {
       Ok(vp) => {
         // This will never fail because the buffer is big enough
         let encoded_size = vp.encode(&mut buf).expect("failed to encode value pointer");
@@ -182,9 +372,21 @@ impl<C: Comparator + Send + Sync + 'static> Wal<C> {
       }
       Err(ValueLogError::NotEnoughSpace { .. }) => {
         let new_fid = self.fid_generator.increment();
-        let vlog = ValueLog::create(CreateOptions::new(new_fid))?;
+        // `with_size(0)` rather than `CreateOptions::new`'s 2GB default: a
+        // mmap-backed log's chunked growth (see `MmapValueLog`) already
+        // floors the first chunk at `CHUNK_SIZE` on its own, so there's no
+        // need to eagerly reserve a whole log's worth of address space for
+        // a shared value log that may end up holding a single small entry.
+        let vlog = ValueLog::create(CreateOptions::new(new_fid).with_size(0))?;
         let vp = vlog
-          .write(meta.version(), key, val, meta.checksum())
+          .write(
+            meta.version(),
+            key,
+            val,
+            meta.checksum(),
+            self.opts.min_compress_len(),
+            self.opts.compression(),
+          )
           .map_err(|e| {
             let _ = vlog.remove();
             e
@@ -265,7 +467,14 @@ impl<C: Comparator + Send + Sync + 'static> Wal<C> {
     let new_fid = self.fid_generator.increment();
     let vlog = ValueLog::create(CreateOptions::new(new_fid).with_size(encoded_entry_size as u64))?;
     let vp = vlog
-      .write(meta.version(), key, val, meta.checksum())
+      .write(
+        meta.version(),
+        key,
+        val,
+        meta.checksum(),
+        self.opts.min_compress_len(),
+        self.opts.compression(),
+      )
       .map_err(|e| {
         let _ = vlog.remove();
         e
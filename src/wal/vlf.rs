@@ -5,6 +5,8 @@ use super::{
   *,
 };
 
+use crate::options::CompressionType;
+
 use core::cell::UnsafeCell;
 
 use error::EncodeHeaderError;
@@ -13,22 +15,69 @@ use error::EncodeHeaderError;
 use mmap::*;
 #[cfg(feature = "std")]
 use mmap_anon::*;
+#[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+use memfd::*;
+use memory::*;
 
+mod backend;
 #[cfg(feature = "std")]
 mod mmap;
 #[cfg(feature = "std")]
 mod mmap_anon;
+#[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+mod memfd;
+mod memory;
 
 #[derive(derive_more::From)]
 enum ValueLogKind {
   Placeholder(Fid),
-  // Memory(MemoryValueLog),
+  Memory(MemoryValueLog),
   #[cfg(feature = "std")]
   Mmap(MmapValueLog),
+  #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+  Memfd(MemfdValueLog),
   #[cfg(feature = "std")]
   MmapAnon(MmapAnonValueLog),
 }
 
+/// Sequential iterator returned by [`ValueLog::entries`].
+///
+/// Only the `Mmap` backing has anything to scan (it is the only kind that
+/// outlives the process, so it is the only one compaction needs to walk);
+/// the other kinds yield no entries.
+#[cfg(feature = "std")]
+pub enum ValueLogEntries<'a> {
+  Mmap(ValueLogIter<'a>),
+  Empty,
+}
+
+#[cfg(feature = "std")]
+impl<'a> Iterator for ValueLogEntries<'a> {
+  type Item = Result<ValueLogEntry<'a>, ValueLogError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self {
+      Self::Mmap(iter) => iter.next(),
+      Self::Empty => None,
+    }
+  }
+}
+
+/// Counts produced by [`ValueLog::compact_into`]'s reclaim pass.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompactionStats {
+  /// Number of entries copied into the destination log because they were
+  /// still referenced by their key's current pointer.
+  pub live_entries: u64,
+  /// Number of entries skipped because a newer write or a removal had
+  /// already superseded them.
+  pub dead_entries: u64,
+  /// Total on-disk bytes (header, key, value, and checksum trailer) the
+  /// skipped dead entries occupied in the source log.
+  pub reclaimed_bytes: u64,
+}
+
 struct EncodedHeader {
   buf: [u8; Header::MAX_ENCODED_SIZE],
   len: usize,
@@ -42,16 +91,46 @@ impl core::ops::Deref for EncodedHeader {
   }
 }
 
+/// The fixed-format header preceding each value-log entry's key and value
+/// bytes on disk.
+///
+/// Encoded as `kl | vl+cks | version | compression | raw_len?`, where `vl`
+/// and `cks` are packed into a single varint by [`Self::encode_vlcks`] and
+/// `raw_len` is only present when `compression` is not
+/// [`CompressionType::None`] (an uncompressed entry's original length is
+/// just `vl`). Transparent per-entry LZ4/Zstd compression (codec and
+/// threshold passed to [`ValueLog::write`] as `compression` and
+/// `min_compress_len`) already lives here rather than in
+/// the `vlcks` varint's spare bits: `compression` is its own tag byte so
+/// unknown future codecs fail closed with
+/// [`ValueLogError::UnsupportedCompression`] instead of silently
+/// misinterpreting packed bits, and `raw_len` sizes the decompression
+/// buffer on read (see [`ValueLog::read_value`]/[`decompress_value`]) since
+/// the checksum is computed over the stored, possibly-compressed bytes.
 struct Header {
   kl: u32,
+  /// The length of the value as stored on disk, i.e. *after* compression.
   vl: u32,
+  /// The checksum the caller already computed over the entry's `Meta`, key
+  /// and value when it was first written to the active log's skiplist (see
+  /// `skl::map::Meta::checksum`), carried along so [`ValueLog::recover`] can
+  /// rebuild a skiplist entry from a replayed value-log record without
+  /// recomputing it. It is not re-verified on an ordinary read: detecting
+  /// bit-rot or a torn write in the bytes actually sitting in the value log
+  /// is [`ValueLog::read_checked`]'s job, which checksums the stored bytes
+  /// themselves rather than trusting this caller-supplied value.
   cks: u32,
   version: u64,
+  /// The compression algorithm the value was stored with.
+  compression: CompressionType,
+  /// The value's length *before* compression. Equal to `vl` when
+  /// `compression` is [`CompressionType::None`].
+  raw_len: u32,
 }
 
 impl Header {
-  const MAX_ENCODED_SIZE: usize = 5 + 10 + 10;
-  const MIN_ENCODED_SIZE: usize = 1 + 1 + 1;
+  const MAX_ENCODED_SIZE: usize = 5 + 10 + 10 + 1 + 5;
+  const MIN_ENCODED_SIZE: usize = 1 + 1 + 1 + 1;
 
   #[inline]
   const fn new(version: u64, kl: usize, vl: usize, cks: u32) -> Self {
@@ -60,6 +139,30 @@ impl Header {
       vl: vl as u32,
       cks,
       version,
+      compression: CompressionType::None,
+      raw_len: vl as u32,
+    }
+  }
+
+  /// Builds a header for a value stored compressed with `compression`; `vl`
+  /// is the stored (compressed) length and `raw_len` is the value's length
+  /// before compression.
+  #[inline]
+  const fn compressed(
+    version: u64,
+    kl: usize,
+    vl: usize,
+    cks: u32,
+    compression: CompressionType,
+    raw_len: usize,
+  ) -> Self {
+    Self {
+      kl: kl as u32,
+      vl: vl as u32,
+      cks,
+      version,
+      compression,
+      raw_len: raw_len as u32,
     }
   }
 
@@ -77,6 +180,17 @@ impl Header {
     // encode version
     cur += encode_varint(self.version, &mut buf[cur..]).map_err(EncodeHeaderError::VarintError)?;
 
+    // encode the compression algorithm
+    buf[cur] = self.compression as u8;
+    cur += 1;
+
+    // the original length is only needed to size the decompression buffer,
+    // so uncompressed entries skip it entirely
+    if !matches!(self.compression, CompressionType::None) {
+      cur +=
+        encode_varint(self.raw_len as u64, &mut buf[cur..]).map_err(EncodeHeaderError::VarintError)?;
+    }
+
     Ok(EncodedHeader { buf, len: cur })
   }
 
@@ -100,6 +214,24 @@ impl Header {
 
     let (vl, cks) = Self::decode_vlcks(vlcks);
 
+    let compression_byte = *buf.get(readed).ok_or(DecodeHeaderError::NotEnoughBytes)?;
+    readed += 1;
+    let compression = match compression_byte {
+      0 => CompressionType::None,
+      1 => CompressionType::Lz4,
+      2 => CompressionType::Zstd,
+      other => return Err(ValueLogError::UnsupportedCompression(other)),
+    };
+
+    let raw_len = if matches!(compression, CompressionType::None) {
+      vl
+    } else {
+      let (raw_len_size, raw_len) =
+        decode_varint(&buf[readed..]).map_err(DecodeHeaderError::VarintError)?;
+      readed += raw_len_size;
+      raw_len as u32
+    };
+
     Ok((
       readed,
       Self {
@@ -107,13 +239,23 @@ impl Header {
         vl,
         cks,
         version,
+        compression,
+        raw_len,
       },
     ))
   }
 
   #[inline]
   const fn encoded_len(&self) -> usize {
-    encoded_len_varint(self.kl as u64) + encoded_len_varint(self.encode_vlcks())
+    let base = encoded_len_varint(self.kl as u64)
+      + encoded_len_varint(self.encode_vlcks())
+      + encoded_len_varint(self.version)
+      + 1;
+    if matches!(self.compression, CompressionType::None) {
+      base
+    } else {
+      base + encoded_len_varint(self.raw_len as u64)
+    }
   }
 
   #[inline]
@@ -129,6 +271,109 @@ impl Header {
   }
 }
 
+/// Compresses `val` with `compression` if it is at least `min_compress_len`
+/// bytes long and the compressed form actually comes out smaller; otherwise
+/// returns `None` and the caller should store `val` verbatim.
+fn maybe_compress(val: &[u8], min_compress_len: u64, compression: CompressionType) -> Option<(CompressionType, std::vec::Vec<u8>)> {
+  if matches!(compression, CompressionType::None) || (val.len() as u64) < min_compress_len {
+    return None;
+  }
+
+  let compressed = match compression {
+    CompressionType::None => return None,
+    CompressionType::Lz4 => lz4_flex::block::compress(val),
+    #[cfg(feature = "zstd")]
+    CompressionType::Zstd => zstd::bulk::compress(val, 0).ok()?,
+    #[cfg(not(feature = "zstd"))]
+    CompressionType::Zstd => return None,
+  };
+
+  (compressed.len() < val.len()).then_some((compression, compressed))
+}
+
+/// How many times larger than `stored` a decompressed value is allowed to
+/// claim to be, in [`decompress_value`]. `raw_len` comes straight off disk
+/// -- attacker- or corruption-controllable, up to `u32::MAX` -- so it has
+/// to be bounded relative to the bytes actually available to decompress
+/// *before* either decompressor allocates an output buffer of that claimed
+/// size; a real value's compression ratio never gets remotely close to
+/// this, but a hand-crafted or bit-flipped entry claiming a multi-GB
+/// `raw_len` for a handful of stored bytes does.
+const MAX_DECOMPRESSION_RATIO: u64 = 1024;
+
+/// Floor for the cap [`MAX_DECOMPRESSION_RATIO`] computes, so a
+/// legitimately tiny `stored` (a handful of highly-compressible bytes)
+/// isn't rejected for decompressing to an ordinary small value.
+const MIN_DECOMPRESSION_BUDGET: u64 = 4 * 1024;
+
+/// Decompresses `stored` (the bytes physically on disk) back into the
+/// original value, using the algorithm and original length recorded in
+/// `header`. Returns `stored` unchanged for [`CompressionType::None`].
+///
+/// `pointer` is only used to label a decompression failure with the log
+/// and offset it came from.
+fn decompress_value<'a>(
+  header: &Header,
+  stored: &'a [u8],
+  pointer: Pointer,
+) -> Result<std::borrow::Cow<'a, [u8]>, ValueLogError> {
+  let corrupted = || ValueLogError::Corrupted {
+    fid: pointer.fid(),
+    offset: pointer.offset(),
+    reason: crate::error::CorruptionReason::Decompression,
+  };
+
+  if !matches!(header.compression, CompressionType::None) {
+    let max_raw_len = (stored.len() as u64)
+      .saturating_mul(MAX_DECOMPRESSION_RATIO)
+      .max(MIN_DECOMPRESSION_BUDGET);
+    if header.raw_len as u64 > max_raw_len {
+      return Err(corrupted());
+    }
+  }
+
+  match header.compression {
+    CompressionType::None => Ok(std::borrow::Cow::Borrowed(stored)),
+    CompressionType::Lz4 => lz4_flex::block::decompress(stored, header.raw_len as usize)
+      .map(std::borrow::Cow::Owned)
+      .map_err(|_| corrupted()),
+    #[cfg(feature = "zstd")]
+    CompressionType::Zstd => zstd::bulk::decompress(stored, header.raw_len as usize)
+      .map(std::borrow::Cow::Owned)
+      .map_err(|_| corrupted()),
+    #[cfg(not(feature = "zstd"))]
+    CompressionType::Zstd => Err(ValueLogError::UnsupportedCompression(
+      CompressionType::Zstd as u8,
+    )),
+  }
+}
+
+// NOTE: there is no GC/compaction driver yet that reclaims the dead bytes a
+// bitcask-style value log accumulates as keys are overwritten or deleted.
+// The design is straightforward on top of what's already here: sequentially
+// walk a read-only [`MmapValueLog`](mmap::MmapValueLog) via
+// [`ValueLog::read`]/[`ValueLog::read_value`], decode each record's key and
+// `Pointer`, and for each one ask the owning `LogFile` (via
+// `LogFile::get(version, key)`) whether its *current* pointer still names
+// this exact `(fid, offset)` -- if so the record is live and gets
+// re-`write`-ten into a fresh destination `ValueLog` (producing a new
+// `Pointer` the caller then has to splice back into the `LogFile` entry);
+// if not, the record is dead space and is skipped. A `gc(threshold: f64)`
+// entry point would track reclaimable bytes the same way (superseding a
+// pointer or removing its key bumps a per-fid discardable-byte counter) and
+// only run the sequential pass once that estimate clears `threshold`,
+// leveldb-style.
+//
+// What blocks wiring this up for real is `LogFile::get`'s return type: it
+// resolves to `EntryRef<'a, C>` from `super::lf::iterator`, but
+// `crate::wal::lf` only declares `mod iterator;` -- there is no
+// `wal/lf/iterator.rs` in this tree, so `LogFile::get` itself doesn't
+// compile today. (The orphaned sibling module at `crate::lf` has a same-named
+// `lf/iterator.rs`, but it resolves `EntryRef` to `crate::types::EntryRef<'a>`,
+// a different, one-generic-parameter type than the two-parameter
+// `EntryRef<'a, C>` `wal/lf.rs`'s signatures expect -- not a drop-in fix.)
+// That gap predates this change and is its own fix, not something a GC
+// feature should paper over by inventing a replacement iterator module.
 /// ValueLog is not thread safe and cannot be used concurrently.
 ///
 /// ```test
@@ -151,9 +396,52 @@ impl ValueLog {
     }
   }
 
+  /// Creates a new value log backed by `opts`.
+  ///
+  /// With the `std` feature enabled: if `opts.in_memory()` is set, the log
+  /// is backed by an anonymous, file-free mapping (see
+  /// [`MmapAnonValueLog`]) instead of a named `.vlog` file, so an
+  /// in-memory table's values never touch the filesystem; otherwise it is
+  /// backed by a growable [`MmapValueLog`] file. On Linux, with the
+  /// `memfd` feature enabled, setting `opts.memfd()` alongside
+  /// `opts.in_memory()` instead backs the log with a `memfd_create` fd
+  /// (see [`MemfdValueLog`]), sized once at creation and kernel-sealed
+  /// read-only once frozen; it does not grow, so a write that does not fit
+  /// returns [`ValueLogError::NotEnoughSpace`].
+  ///
+  /// Without `std` (`no_std` + `alloc`), mmap isn't available at all, so
+  /// this always falls back to the same `Vec`-backed [`MemoryValueLog`]
+  /// [`Self::create_in_memory`] uses, regardless of `opts.in_memory()`.
   pub fn create(opts: CreateOptions) -> Result<Self, ValueLogError> {
+    #[cfg(feature = "std")]
+    let kind = if opts.in_memory {
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      if opts.memfd {
+        return Ok(Self {
+          kind: UnsafeCell::new(ValueLogKind::Memfd(MemfdValueLog::create(opts)?)),
+        });
+      }
+
+      ValueLogKind::MmapAnon(MmapAnonValueLog::create(opts)?)
+    } else {
+      ValueLogKind::Mmap(MmapValueLog::create(opts)?)
+    };
+
+    #[cfg(not(feature = "std"))]
+    let kind = ValueLogKind::Memory(MemoryValueLog::create(opts)?);
+
+    Ok(Self {
+      kind: UnsafeCell::new(kind),
+    })
+  }
+
+  /// Creates a new value log backed by a plain in-memory buffer (see
+  /// [`MemoryValueLog`]), for unit tests and embedded/flash targets that
+  /// have no file system, not even the anonymous mapping [`ValueLog::create`]
+  /// falls back to for `opts.in_memory()` when `std` is enabled.
+  pub fn create_in_memory(opts: CreateOptions) -> Result<Self, ValueLogError> {
     Ok(Self {
-      kind: UnsafeCell::new(ValueLogKind::Mmap(MmapValueLog::create(opts)?)),
+      kind: UnsafeCell::new(ValueLogKind::Memory(MemoryValueLog::create(opts)?)),
     })
   }
 
@@ -169,20 +457,39 @@ impl ValueLog {
     match self.kind_mut() {
       ValueLogKind::Mmap(vlf) => vlf.remove(),
       ValueLogKind::MmapAnon(vlf) => vlf.remove(),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.remove(),
+      ValueLogKind::Memory(vlf) => vlf.remove(),
       ValueLogKind::Placeholder(_) => Ok(()),
     }
   }
 
+  /// Writes `key`/`value` to the log, compressing `value` with `compression`
+  /// first if it is at least `min_compress_len` bytes and doing so actually
+  /// shrinks it; otherwise the value is stored verbatim.
   pub fn write(
     &self,
     version: u64,
     key: &[u8],
     value: &[u8],
     checksum: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
   ) -> Result<Pointer, ValueLogError> {
     match self.kind_mut() {
-      ValueLogKind::Mmap(vlf) => vlf.write(version, key, value, checksum),
-      ValueLogKind::MmapAnon(vlf) => vlf.write(version, key, value, checksum),
+      #[cfg(feature = "std")]
+      ValueLogKind::Mmap(vlf) => vlf.write(version, key, value, checksum, min_compress_len, compression),
+      #[cfg(feature = "std")]
+      ValueLogKind::MmapAnon(vlf) => {
+        vlf.write(version, key, value, checksum, min_compress_len, compression)
+      }
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => {
+        vlf.write(version, key, value, checksum, min_compress_len, compression)
+      }
+      ValueLogKind::Memory(vlf) => {
+        vlf.write(version, key, value, checksum, min_compress_len, compression)
+      }
       ValueLogKind::Placeholder(_) => Err(ValueLogError::NotEnoughSpace {
         required: self.encoded_entry_size(version, key, value, checksum) as u64,
         remaining: 0,
@@ -193,8 +500,13 @@ impl ValueLog {
   /// Returns a byte slice which contains header, key and value.
   pub(crate) fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
     match self.kind() {
+      #[cfg(feature = "std")]
       ValueLogKind::Mmap(vlf) => vlf.read(offset, size),
+      #[cfg(feature = "std")]
       ValueLogKind::MmapAnon(vlf) => vlf.read(offset, size),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.read(offset, size),
+      ValueLogKind::Memory(vlf) => vlf.read(offset, size),
       ValueLogKind::Placeholder(_) => Err(ValueLogError::OutOfBound {
         offset,
         len: size,
@@ -203,6 +515,77 @@ impl ValueLog {
     }
   }
 
+  /// Reads the value `pointer` refers to, decoding its header and
+  /// decompressing it if it was stored compressed.
+  ///
+  /// Compression defeats [`ValueLog::read`]'s zero-copy `&[u8]`, since the
+  /// bytes on disk are no longer the value itself, so this returns a
+  /// [`Cow`](std::borrow::Cow): a borrow for entries stored uncompressed,
+  /// an owned buffer for entries that had to be decompressed.
+  ///
+  /// `pointer` itself keeps recording the on-disk (i.e. compressed, when
+  /// compression applied) length: offsets into the log only ever need to
+  /// skip over what's actually stored there, and `Header::raw_len` is the
+  /// only place the original length has to be known, to size the
+  /// decompression buffer below.
+  pub fn read_value(&self, pointer: Pointer) -> Result<std::borrow::Cow<'_, [u8]>, ValueLogError> {
+    let buf = self.read(pointer.offset() as usize, pointer.size() as usize)?;
+    let (header_len, header) = Header::decode(buf)?;
+    let kl = header.kl as usize;
+    let vl = header.vl as usize;
+    let start = header_len + kl;
+    let stored = buf
+      .get(start..start + vl)
+      .ok_or(DecodeHeaderError::NotEnoughBytes)?;
+    decompress_value(&header, stored, pointer)
+  }
+
+  /// Like [`ValueLog::read`], but for the `Mmap` and `Memory` backings
+  /// recomputes and compares the entry's trailing CRC32 before returning
+  /// the slice, catching bit-rot (or, for `Memory`, a buggy caller mutating
+  /// the buffer) that the unchecked read would silently trust. Backings
+  /// with no on-disk checksum trailer (`MmapAnon`, `Memfd`, or a
+  /// placeholder) fall back to an unchecked read.
+  ///
+  /// This deliberately checksums the header+key+value bytes as written,
+  /// not the [`Header::cks`](Header) field: `cks` is the skiplist entry's
+  /// checksum from *before* it reached the value log (see the field's own
+  /// doc comment), so comparing it against a checksum recomputed here would
+  /// only ever catch the same corruption the trailer already catches, while
+  /// adding a second, narrower code path that misses corruption in the
+  /// header or key bytes `cks` was never computed over in the first place.
+  /// A fixed CRC32 trailer is used rather than a caller-selectable algorithm
+  /// for the same reason `compression` got its own tag byte instead of
+  /// packed `vlcks` bits: a pluggable, variable-width trailer would force
+  /// every chunk-boundary and entry-size calculation in
+  /// [`MmapValueLog`](crate::wal::vlf::MmapValueLog) to branch on which
+  /// algorithm wrote a given entry, for a dimension (speed vs. integrity)
+  /// nothing in this crate's call sites currently needs to tune.
+  ///
+  /// A mismatch surfaces as [`ValueLogError::Corrupted`] with
+  /// [`CorruptionReason::ChecksumMismatch`](crate::error::CorruptionReason),
+  /// not a dedicated `fid`/`offset` variant of its own: a bad trailer and a
+  /// truncated or undecodable entry are the same kind of fact (something at
+  /// this offset in this log isn't trustworthy), so callers already match on
+  /// one `Corrupted { fid, offset, reason }` shape for all of it instead of
+  /// juggling several differently-shaped "this log is bad" errors.
+  pub(crate) fn read_checked(&self, pointer: Pointer) -> Result<&[u8], ValueLogError> {
+    match self.kind() {
+      #[cfg(feature = "std")]
+      ValueLogKind::Mmap(vlf) => vlf.read_checked(pointer),
+      #[cfg(feature = "std")]
+      ValueLogKind::MmapAnon(vlf) => vlf.read(pointer.offset() as usize, pointer.size() as usize),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.read(pointer.offset() as usize, pointer.size() as usize),
+      ValueLogKind::Memory(vlf) => vlf.read_checked(pointer),
+      ValueLogKind::Placeholder(_) => Err(ValueLogError::OutOfBound {
+        offset: pointer.offset() as usize,
+        len: pointer.size() as usize,
+        size: 0,
+      }),
+    }
+  }
+
   /// Returns the encoded entry size for the given key and value.
   pub(crate) fn encoded_entry_size(&self, version: u64, key: &[u8], val: &[u8], cks: u32) -> usize {
     let kl = key.len();
@@ -211,11 +594,123 @@ impl ValueLog {
     h.encoded_len() + kl + vl
   }
 
+  /// Returns a sequential iterator over every entry in the log, from offset
+  /// `0` forward, for a compaction or garbage-collection pass to walk
+  /// rather than the random point lookups [`ValueLog::read_value`] makes.
+  ///
+  /// For each entry, compare [`ValueLogEntry::pointer`] against the pointer
+  /// the key's current skiplog entry holds (see
+  /// [`ValueLogEntry::is_live`]) to tell a still-referenced value apart
+  /// from a dead one a rewrite can drop.
+  #[cfg(feature = "std")]
+  pub fn entries(&self) -> ValueLogEntries<'_> {
+    match self.kind() {
+      ValueLogKind::Mmap(vlf) => ValueLogEntries::Mmap(vlf.iter()),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(_) => ValueLogEntries::Empty,
+      ValueLogKind::MmapAnon(_) | ValueLogKind::Memory(_) | ValueLogKind::Placeholder(_) => {
+        ValueLogEntries::Empty
+      }
+    }
+  }
+
+  /// Sequentially walks `self` via [`Self::entries`] and copies every entry
+  /// still referenced by its key's current pointer into `dest`, the same
+  /// bitcask-style reclaim pass described in the NOTE above [`ValueLog`]:
+  /// `current_pointer(key)` is asked for the pointer the key's skiplist
+  /// entry holds *right now*, [`ValueLogEntry::is_live`] compares it
+  /// against the entry being scanned, and a dead entry (superseded or
+  /// removed) is skipped and counted as reclaimed rather than copied.
+  ///
+  /// A live entry is read back out decompressed (via [`Self::read_value`])
+  /// and re-written into `dest` with `compression`/`min_compress_len`
+  /// applied the same way [`Self::write`] always applies them; the
+  /// resulting new [`Pointer`] is handed to `on_relocated(key, pointer)` so
+  /// the caller can splice it back into the key's `LogFile` entry.
+  ///
+  /// This function only performs the scan-and-rewrite itself -- it takes
+  /// `current_pointer`/`on_relocated` as caller-supplied callbacks rather
+  /// than reaching into a `LogFile` directly, because `LogFile::get` (the
+  /// obvious source for `current_pointer`) doesn't compile in this tree
+  /// yet: `crate::wal::lf` declares `mod iterator;` but there is no
+  /// `wal/lf/iterator.rs`, so that gap has to be fixed on its own before a
+  /// `Db`-level GC driver can supply real callbacks here. Passing them in
+  /// keeps this method itself fully working and testable today (a test can
+  /// back `current_pointer` with a plain `HashMap`) without papering over
+  /// that unrelated blocker.
+  #[cfg(feature = "std")]
+  pub fn compact_into<F, G>(
+    &self,
+    dest: &ValueLog,
+    min_compress_len: u64,
+    compression: CompressionType,
+    mut current_pointer: F,
+    mut on_relocated: G,
+  ) -> Result<CompactionStats, ValueLogError>
+  where
+    F: FnMut(&[u8]) -> Option<Pointer>,
+    G: FnMut(&[u8], Pointer),
+  {
+    let mut stats = CompactionStats::default();
+
+    for entry in self.entries() {
+      let entry = entry?;
+
+      if !entry.is_live(current_pointer(entry.key())) {
+        stats.dead_entries += 1;
+        stats.reclaimed_bytes += entry.entry_len();
+        continue;
+      }
+
+      let value = self.read_value(entry.pointer())?;
+      let new_pointer = dest.write(
+        entry.version(),
+        entry.key(),
+        &value,
+        entry.checksum(),
+        min_compress_len,
+        compression,
+      )?;
+      on_relocated(entry.key(), new_pointer);
+      stats.live_entries += 1;
+    }
+
+    Ok(stats)
+  }
+
+  /// Scans the log from offset `0` and returns the offset just past the
+  /// last entry that decodes and checksums cleanly, without modifying the
+  /// log. A torn or corrupt tail a crash left behind is the
+  /// `len() - offset` bytes past the returned offset: [`ValueLog::rewind`]
+  /// takes that byte count, not the offset, and a log reopened with
+  /// [`ValueLog::open`] is read-only and refuses to rewind.
+  ///
+  /// Only the `Mmap` backing can have a torn write (it is the only kind
+  /// that outlives the process); the other kinds have nothing to recover
+  /// from and simply report their current length.
+  pub fn recover(&self) -> u64 {
+    match self.kind() {
+      #[cfg(feature = "std")]
+      ValueLogKind::Mmap(vlf) => vlf.last_valid_offset(),
+      #[cfg(feature = "std")]
+      ValueLogKind::MmapAnon(vlf) => vlf.len() as u64,
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.len() as u64,
+      ValueLogKind::Memory(vlf) => vlf.len() as u64,
+      ValueLogKind::Placeholder(_) => 0,
+    }
+  }
+
   #[inline]
   pub fn rewind(&self, size: usize) -> Result<(), ValueLogError> {
     match self.kind_mut() {
+      #[cfg(feature = "std")]
       ValueLogKind::Mmap(vlf) => vlf.rewind(size),
+      #[cfg(feature = "std")]
       ValueLogKind::MmapAnon(vlf) => vlf.rewind(size),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.rewind(size),
+      ValueLogKind::Memory(vlf) => vlf.rewind(size),
       ValueLogKind::Placeholder(_) => Ok(()),
     }
   }
@@ -223,8 +718,13 @@ impl ValueLog {
   #[inline]
   pub fn len(&self) -> usize {
     match self.kind() {
+      #[cfg(feature = "std")]
       ValueLogKind::Mmap(vlf) => vlf.len(),
+      #[cfg(feature = "std")]
       ValueLogKind::MmapAnon(vlf) => vlf.len(),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.len(),
+      ValueLogKind::Memory(vlf) => vlf.len(),
       ValueLogKind::Placeholder(_) => 0,
     }
   }
@@ -232,8 +732,13 @@ impl ValueLog {
   #[inline]
   pub fn capacity(&self) -> u64 {
     match self.kind() {
+      #[cfg(feature = "std")]
       ValueLogKind::Mmap(vlf) => vlf.capacity(),
+      #[cfg(feature = "std")]
       ValueLogKind::MmapAnon(vlf) => vlf.capacity(),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.capacity(),
+      ValueLogKind::Memory(vlf) => vlf.capacity(),
       ValueLogKind::Placeholder(_) => 0,
     }
   }
@@ -241,8 +746,13 @@ impl ValueLog {
   #[inline]
   pub fn remaining(&self) -> u64 {
     match self.kind() {
+      #[cfg(feature = "std")]
       ValueLogKind::Mmap(vlf) => vlf.remaining(),
+      #[cfg(feature = "std")]
       ValueLogKind::MmapAnon(vlf) => vlf.remaining(),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.remaining(),
+      ValueLogKind::Memory(vlf) => vlf.remaining(),
       ValueLogKind::Placeholder(_) => 0,
     }
   }
@@ -250,8 +760,13 @@ impl ValueLog {
   #[inline]
   pub fn fid(&self) -> Fid {
     match self.kind() {
+      #[cfg(feature = "std")]
       ValueLogKind::Mmap(vlf) => vlf.fid(),
+      #[cfg(feature = "std")]
       ValueLogKind::MmapAnon(vlf) => vlf.fid(),
+      #[cfg(all(feature = "std", feature = "memfd", target_os = "linux"))]
+      ValueLogKind::Memfd(vlf) => vlf.fid(),
+      ValueLogKind::Memory(vlf) => vlf.fid(),
       ValueLogKind::Placeholder(fid) => *fid,
     }
   }
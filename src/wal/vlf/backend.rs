@@ -0,0 +1,48 @@
+use super::*;
+
+/// The storage surface a value log needs: append-only writes, random-access
+/// reads, and the bookkeeping to undo a partial write or tear the log down.
+///
+/// [`MmapValueLog`] and [`MmapAnonValueLog`] both hard-code `memmap2` as
+/// their backing store; [`MemoryValueLog`] backs the same surface with a
+/// plain growable buffer instead, for tests and embedded targets that have
+/// no file system (or mmap) to speak of. Each type keeps its existing
+/// inherent methods as the call site used by [`ValueLogKind`]'s dispatch;
+/// this trait exists so code that only needs the storage surface - not a
+/// specific backing - can be written once against it.
+pub(super) trait ValueLogBackend {
+  /// Writes `key`/`val` to the log, compressing `val` with `compression`
+  /// first if it is at least `min_compress_len` bytes and doing so actually
+  /// shrinks it; otherwise the value is stored verbatim.
+  fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError>;
+
+  /// Returns a byte slice which contains header, key and value.
+  fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError>;
+
+  /// Discards the trailing `size` bytes, undoing a write that was never
+  /// committed to the index.
+  fn rewind(&mut self, size: usize) -> Result<(), ValueLogError>;
+
+  /// Returns the number of bytes currently written to the log.
+  fn len(&self) -> usize;
+
+  /// Returns the log's total capacity in bytes.
+  fn capacity(&self) -> u64;
+
+  /// Returns the number of bytes still available before the log is full.
+  fn remaining(&self) -> u64;
+
+  /// Returns the id of this value log.
+  fn fid(&self) -> Fid;
+
+  /// Tears down the backing storage.
+  fn remove(&mut self) -> Result<(), ValueLogError>;
+}
@@ -13,9 +13,19 @@ pub enum Error {
   /// Returned when the value log checksum mismatch.
   #[cfg_attr(feature = "std", error("value log checksum mismatch"))]
   ChecksumMismatch,
-  /// Returned when the value log is corrupted.
-  #[cfg_attr(feature = "std", error("value log is corrupted"))]
-  Corrupted,
+  /// Returned when an entry's checksum does not match its stored header,
+  /// key and value bytes, or the bytes needed to verify it run past the
+  /// log's current length.
+  #[cfg_attr(
+    feature = "std",
+    error("value log {fid} is corrupted at offset {offset}")
+  )]
+  Corrupted {
+    /// The id of the value log the corrupt entry was read from.
+    fid: crate::Fid,
+    /// The offset of the corrupt entry within that log.
+    offset: u64,
+  },
 
   /// Returned when the value log does not have enough space to hold the value.
   #[cfg_attr(feature = "std", error("value log does not have enough space to hold the value, required: {required}, remaining: {remaining}"))]
@@ -49,7 +59,9 @@ impl core::fmt::Display for Error {
       Error::Closed => write!(f, "value log is closed"),
       Error::ReadOnly => write!(f, "value log is read only"),
       Error::ChecksumMismatch => write!(f, "value log checksum mismatch"),
-      Error::Corrupted => write!(f, "value log is corrupted"),
+      Error::Corrupted { fid, offset } => {
+        write!(f, "value log {fid} is corrupted at offset {offset}")
+      }
       Error::NotEnoughSpace {
         required,
         remaining,
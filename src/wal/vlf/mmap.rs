@@ -1,14 +1,93 @@
 use core::cell::RefCell;
-use std::{fmt::Write, fs::File, io::Write as _};
+use std::{
+  fmt::Write,
+  fs::File,
+  io::{Read, Seek, SeekFrom, Write as _},
+};
 
 use fs4::FileExt;
 use memmap2::{Mmap, MmapMut, MmapOptions};
 
-use super::{error::Error, options::*, *};
+use super::{
+  error::{ChecksumMismatch, ValueLogError},
+  options::*,
+  *,
+};
 
 const EXTENSION: &str = "vlog";
 const CHECKSUM_OVERHEAD: u64 = 4;
 
+/// Identifies a file as a `MmapValueLog`, distinct from any other file that
+/// happens to share the `.vlog` extension or be mapped at the same path.
+const MAGIC: [u8; 7] = *b"LMNVLOG";
+/// Bumped whenever the on-disk layout below `MAGIC`/`VERSION` changes in a
+/// way `MmapValueLog::open` needs to reject rather than misread.
+const VERSION: u8 = 1;
+/// `MAGIC` (7 bytes) + `VERSION` (1 byte) + `fid` (8 bytes) + declared
+/// capacity (8 bytes) + a CRC32 over all of the above (4 bytes).
+const HEADER_LEN: u64 = 7 + 1 + 8 + 8 + 4;
+
+/// Encodes the fixed-size header written at offset `0` on [`MmapValueLog::create`]
+/// and validated by [`MmapValueLog::open`]: a magic tag and format version so a
+/// wrong/corrupt/foreign file is rejected up front instead of silently mapped,
+/// the fid and declared capacity so the file can be identified without
+/// cross-referencing the manifest, and a CRC so a torn or bit-flipped header
+/// is itself detected rather than trusted.
+fn encode_header(fid: Fid, capacity: u64) -> [u8; HEADER_LEN as usize] {
+  let mut buf = [0u8; HEADER_LEN as usize];
+  let mut cur = 0;
+  buf[cur..cur + MAGIC.len()].copy_from_slice(&MAGIC);
+  cur += MAGIC.len();
+  buf[cur] = VERSION;
+  cur += 1;
+  buf[cur..cur + 8].copy_from_slice(&fid.as_u64().to_le_bytes());
+  cur += 8;
+  buf[cur..cur + 8].copy_from_slice(&capacity.to_le_bytes());
+  cur += 8;
+  let crc = crc32fast::hash(&buf[..cur]);
+  buf[cur..cur + 4].copy_from_slice(&crc.to_le_bytes());
+  buf
+}
+
+/// Reads and validates the header written by [`encode_header`], returning
+/// the fid and declared capacity it records.
+fn decode_and_validate_header(header: &[u8], expected_fid: Fid) -> Result<(Fid, u64), ValueLogError> {
+  if header.len() < HEADER_LEN as usize || header[..MAGIC.len()] != MAGIC {
+    return Err(ValueLogError::WrongMagic);
+  }
+
+  let mut cur = MAGIC.len();
+  let version = header[cur];
+  cur += 1;
+  if version != VERSION {
+    return Err(ValueLogError::UnsupportedVersion(version));
+  }
+
+  let fid = Fid::new(u64::from_le_bytes(header[cur..cur + 8].try_into().unwrap()));
+  cur += 8;
+  let capacity = u64::from_le_bytes(header[cur..cur + 8].try_into().unwrap());
+  cur += 8;
+  let crc = u32::from_le_bytes(header[cur..cur + 4].try_into().unwrap());
+
+  if crc32fast::hash(&header[..cur]) != crc {
+    return Err(ValueLogError::ChecksumMismatch(ChecksumMismatch));
+  }
+
+  if fid != expected_fid {
+    return Err(ValueLogError::FidMismatch {
+      expected: expected_fid,
+      found: fid,
+    });
+  }
+
+  Ok((fid, capacity))
+}
+
+/// Size of each chunk [`MmapValueLog`] maps as it grows. Kept well above any
+/// single entry this log is asked to store, so [`MmapValueLog::reserve`]
+/// never has to pad across more than one chunk boundary.
+const CHUNK_SIZE: u64 = 64 * 1024 * 1024;
+
 std::thread_local! {
   static BUF: RefCell<std::string::String> = RefCell::new(std::string::String::with_capacity(11));
 }
@@ -22,7 +101,7 @@ enum Memmap {
   },
   MapMut {
     backed: File,
-    mmap: MmapMut,
+    chunks: std::vec::Vec<MmapMut>,
     lock: bool,
   },
 }
@@ -37,15 +116,20 @@ impl Memmap {
       }
       Memmap::MapMut {
         backed,
+        chunks,
         lock,
-        ref mut mmap,
       } => {
-        let cks = crc32fast::hash(&mmap[..size as usize]);
-        mmap[size as usize..size as usize + CHECKSUM_OVERHEAD as usize]
-          .copy_from_slice(&cks.to_le_bytes());
+        let cks = MmapValueLog::hash_prefix(chunks, size);
 
-        if let Err(e) = backed.set_len(size + CHECKSUM_OVERHEAD) {
+        // `size` is logical data length, starting past the header this log
+        // was created with; the trailer goes right after the data on disk.
+        if let Err(e) = backed.set_len(HEADER_LEN + size + CHECKSUM_OVERHEAD) {
           tracing::error!(err=%e, "failed to truncate value log");
+        } else if let Err(e) = backed
+          .seek(SeekFrom::Start(HEADER_LEN + size))
+          .and_then(|_| backed.write_all(&cks.to_le_bytes()))
+        {
+          tracing::error!(err=%e, "failed to write value log trailer");
         }
 
         if let Err(e) = backed.flush() {
@@ -65,17 +149,33 @@ impl Memmap {
   }
 }
 
+/// Grows by mapping additional fixed-size chunks (see [`Self::grow`]) as
+/// entries fill the current one, rather than parity-db's approach of
+/// reserving one large virtual region up front and remapping it as the
+/// backing file is extended. Both give a writable log that, unless
+/// [`CreateOptions::max_size`] caps it, never fails with
+/// [`ValueLogError::NotEnoughSpace`], and that never invalidates a
+/// previously returned `&[u8]`; chunking gets there without needing a
+/// platform-specific fixed/`MAP_FIXED` remap at all, at the cost of
+/// [`Self::locate`] walking the chunk list on every offset translation.
 pub struct MmapValueLog {
-  fid: u32,
+  fid: Fid,
   buf: Memmap,
   len: u64,
   cap: u64,
   ro: bool,
+  /// Ceiling on how far `cap` is allowed to grow; `None` means unbounded.
+  /// See [`CreateOptions::max_size`].
+  max_cap: Option<u64>,
+  /// Declared fixed record size for [`Self::write_uniform`]/[`Self::read_uniform`];
+  /// `None` means the log uses the ordinary `Header`-framed variable-length
+  /// layout. See [`CreateOptions::uniform`].
+  uniform: Option<u64>,
 }
 
 impl MmapValueLog {
   #[inline]
-  pub fn create(opts: CreateOptions) -> Result<Self, Error> {
+  pub fn create(opts: CreateOptions) -> Result<Self, ValueLogError> {
     BUF.with(|buf| {
       let mut buf = buf.borrow_mut();
       buf.clear();
@@ -86,29 +186,44 @@ impl MmapValueLog {
         .create_new(true)
         .open(buf.as_str())?;
 
-      file.set_len(opts.size.saturating_add(CHECKSUM_OVERHEAD))?;
-
       if opts.lock {
         file.lock_exclusive()?;
       }
 
-      let mmap = unsafe { MmapOptions::new().map_mut(&file)? };
+      // `opts.size` used to reserve the whole log as one fixed mapping;
+      // now it only floors the first chunk (plus room for the entry's
+      // checksum trailer), so a log explicitly sized to fit one oversized
+      // entry (see `sync::Wal::insert_entry_to_standalone_vlog`) still
+      // gets a single chunk big enough for it, while the common case
+      // starts at one `CHUNK_SIZE` chunk and grows on demand.
+      let initial = opts.size.saturating_add(CHECKSUM_OVERHEAD).max(CHUNK_SIZE);
+
+      {
+        let mut file = &file;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&encode_header(opts.fid, initial))?;
+      }
+
+      let mut chunks = std::vec::Vec::new();
+      Self::grow(&file, &mut chunks, initial)?;
 
       Ok(Self {
         fid: opts.fid,
         buf: Memmap::MapMut {
           backed: file,
-          mmap,
+          chunks,
           lock: opts.lock,
         },
         len: 0,
-        cap: opts.size,
+        cap: initial,
         ro: false,
+        max_cap: opts.max_size(),
+        uniform: opts.uniform(),
       })
     })
   }
 
-  pub fn open(opts: OpenOptions) -> Result<Self, Error> {
+  pub fn open(opts: OpenOptions) -> Result<Self, ValueLogError> {
     BUF.with(|buf| {
       let mut buf = buf.borrow_mut();
       buf.clear();
@@ -119,11 +234,24 @@ impl MmapValueLog {
         file.lock_exclusive()?;
       }
 
-      let cap = file.metadata()?.len();
+      let mut header = [0u8; HEADER_LEN as usize];
+      {
+        let mut reader = &file;
+        reader.seek(SeekFrom::Start(0))?;
+        reader.read_exact(&mut header)?;
+      }
+      decode_and_validate_header(&header, opts.fid)?;
 
-      let mmap = unsafe { MmapOptions::new().map(&file)? };
+      let cap = file.metadata()?.len() - HEADER_LEN;
 
-      Ok(Self {
+      let mmap = unsafe {
+        MmapOptions::new()
+          .offset(HEADER_LEN)
+          .len(cap as usize)
+          .map(&file)?
+      };
+
+      let mut vlf = Self {
         fid: opts.fid,
         buf: Memmap::Map {
           backed: file,
@@ -133,59 +261,375 @@ impl MmapValueLog {
         len: cap - CHECKSUM_OVERHEAD,
         cap: cap - CHECKSUM_OVERHEAD,
         ro: true,
-      })
+        // A log opened read-only never grows (`ro` already rejects writes),
+        // so there is no ceiling to enforce; `max_cap` only matters to a
+        // `create`-d, writable log.
+        max_cap: None,
+        // Likewise, `uniform` only gates `write_uniform`'s framing choice;
+        // `read`/`read_checked`/`read_uniform` all locate a record from an
+        // offset (and, for `read_uniform`, the record size the caller
+        // already knows out of band), so a read-only log never needs to
+        // recall whether it was written in uniform mode.
+        uniform: None,
+      };
+
+      if opts.recovery_mode == ValueLogRecoveryMode::Tolerant {
+        vlf.truncate_to_last_valid();
+      }
+
+      Ok(vlf)
     })
   }
 
-  #[inline]
-  pub fn write(&mut self, data: &[u8]) -> Result<ValuePointer, Error> {
-    if self.ro {
-      return Err(Error::ReadOnly);
+  /// Walks every entry from offset `0`, via [`Self::iter`], and rewinds the
+  /// log back to the end of the last one that validates, discarding a torn
+  /// or corrupt tail instead of letting it surface from a later read.
+  ///
+  /// Used by [`Self::open`] when [`ValueLogRecoveryMode::Tolerant`] is set;
+  /// mirrors the recovery the manifest performs for a torn trailing record.
+  fn truncate_to_last_valid(&mut self) {
+    self.len = self.last_valid_offset();
+  }
+
+  /// Scans every entry from offset `0`, via [`Self::iter`], and returns the
+  /// offset just past the last one that validates. A log with no entries,
+  /// or whose first entry is already invalid, recovers to offset `0`. The
+  /// scan stops at the first entry that fails to decode or checksum; a
+  /// mapped but never-written (all-zero) region past the last good entry
+  /// fails its checksum the same way, so it also ends the scan, with the
+  /// same warning.
+  ///
+  /// Used by [`Self::truncate_to_last_valid`] and exposed as
+  /// [`ValueLog::recover`](super::ValueLog::recover) so a caller can scan a
+  /// log without committing to truncating it immediately.
+  pub(crate) fn last_valid_offset(&self) -> u64 {
+    let mut last_valid_end = 0u64;
+
+    for entry in self.iter() {
+      match entry {
+        Ok(entry) => last_valid_end = entry.entry_offset() + entry.entry_len(),
+        Err(err) => {
+          tracing::warn!(
+            fid = %self.fid,
+            offset = %last_valid_end,
+            err = %err,
+            "value log entry failed to validate; stopping recovery scan"
+          );
+          break;
+        }
+      }
     }
 
-    match self.buf {
-      Memmap::MapMut { ref mut mmap, .. } => {
-        let len = data.len();
-        let offset = self.len as usize;
-        if offset as u64 + len as u64 + CHECKSUM_OVERHEAD > self.cap {
-          return Err(Error::NotEnoughSpace {
-            required: len as u64,
-            remaining: self.cap - offset as u64,
+    last_valid_end
+  }
+
+  /// Returns the total length currently mapped across `chunks`.
+  fn total_len(chunks: &[MmapMut]) -> u64 {
+    chunks.iter().map(|c| c.len() as u64).sum()
+  }
+
+  /// Translates a log-wide byte `offset` into `(chunk_index, intra_chunk
+  /// offset)`. If `offset` lands past every currently-mapped chunk, returns
+  /// `(chunks.len(), offset - total_len(chunks))`.
+  fn locate(chunks: &[MmapMut], offset: u64) -> (usize, usize) {
+    let mut remaining = offset;
+    for (idx, chunk) in chunks.iter().enumerate() {
+      let len = chunk.len() as u64;
+      if remaining < len {
+        return (idx, remaining as usize);
+      }
+      remaining -= len;
+    }
+    (chunks.len(), remaining as usize)
+  }
+
+  /// Appends a new chunk of exactly `additional` bytes, extending the
+  /// backing file and mapping just the new region so every previously
+  /// mapped chunk, and every slice handed out from it, stays valid.
+  ///
+  /// `chunks` is logical data space, starting at `0`; physically every
+  /// chunk sits [`HEADER_LEN`] bytes further into the file, past the header
+  /// [`MmapValueLog::create`] writes up front.
+  fn grow(file: &File, chunks: &mut std::vec::Vec<MmapMut>, additional: u64) -> Result<(), ValueLogError> {
+    let old_len = Self::total_len(chunks);
+    file.set_len(HEADER_LEN + old_len + additional)?;
+    let chunk = unsafe {
+      MmapOptions::new()
+        .offset(HEADER_LEN + old_len)
+        .len(additional as usize)
+        .map_mut(file)?
+    };
+    chunks.push(chunk);
+    Ok(())
+  }
+
+  /// Returns the offset `entry_len` bytes should actually be written at,
+  /// starting from `offset`: unchanged if the chunk `offset` falls in has
+  /// room for the whole entry, otherwise padded forward to the start of
+  /// the next chunk (growing one first if none exists yet). Assumes
+  /// `entry_len <= CHUNK_SIZE`, true for every entry this value log is
+  /// asked to store.
+  ///
+  /// `max_cap`, when set (see [`CreateOptions::max_size`]), caps how far
+  /// this is allowed to grow the log: a write that would need to map past
+  /// it fails with [`ValueLogError::NotEnoughSpace`] instead, the same
+  /// error a fixed-capacity log already returns once it's full, so callers
+  /// roll over to a new log file either way.
+  fn reserve(
+    file: &File,
+    chunks: &mut std::vec::Vec<MmapMut>,
+    offset: u64,
+    entry_len: u64,
+    max_cap: Option<u64>,
+  ) -> Result<u64, ValueLogError> {
+    let (chunk_idx, intra_offset) = Self::locate(chunks, offset);
+    let chunk_len = chunks.get(chunk_idx).map(|c| c.len() as u64).unwrap_or(0);
+
+    let offset = if intra_offset as u64 + entry_len <= chunk_len {
+      offset
+    } else {
+      offset + (chunk_len - intra_offset as u64)
+    };
+
+    let have = Self::total_len(chunks);
+    let needed_end = offset + entry_len;
+    if needed_end > have {
+      if let Some(max_cap) = max_cap {
+        if needed_end > max_cap {
+          return Err(ValueLogError::NotEnoughSpace {
+            required: needed_end - have,
+            remaining: max_cap.saturating_sub(have),
           });
         }
+      }
+      Self::grow(file, chunks, (needed_end - have).max(CHUNK_SIZE))?;
+    }
+
+    Ok(offset)
+  }
 
-        mmap[offset..offset + len].copy_from_slice(data);
-        let cks = crc32fast::hash(&mmap[offset..offset + len]);
-        mmap[offset + len..offset + len + CHECKSUM_OVERHEAD as usize].copy_from_slice(&cks.to_le_bytes());
-        self.len += len as u64 + CHECKSUM_OVERHEAD;
-        Ok(ValuePointer::new(self.fid, len as u64, offset as u64))
+  /// Hashes the first `len` bytes of the log across however many chunks
+  /// they span, for the whole-log trailer checksum written in
+  /// [`Memmap::unmount`].
+  fn hash_prefix(chunks: &[MmapMut], len: u64) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    let mut remaining = len;
+    for chunk in chunks {
+      if remaining == 0 {
+        break;
       }
-      Memmap::Map { .. } => Err(Error::ReadOnly),
-      _ => Err(Error::Closed),
+      let take = remaining.min(chunk.len() as u64) as usize;
+      hasher.update(&chunk[..take]);
+      remaining -= take as u64;
     }
+    hasher.finalize()
   }
 
+  /// Writes `key`/`val` to the log, compressing `val` with `compression`
+  /// first if it is at least `min_compress_len` bytes and doing so actually
+  /// shrinks it; otherwise the value is stored verbatim. Mirrors
+  /// [`MmapAnonValueLog::write`](super::mmap_anon::MmapAnonValueLog::write),
+  /// plus the trailing per-entry checksum this backing is persisted with.
+  ///
+  /// Unlike a fixed-size mapping, this only runs out of space when
+  /// [`CreateOptions::max_size`] is set and the entry would map past it;
+  /// otherwise, once an entry would overrun the chunk it starts in,
+  /// [`MmapValueLog::reserve`] pads forward into a freshly grown chunk.
   #[inline]
-  pub fn read(&self, offset: usize, size: usize) -> Result<&[u8], Error> {
-    Ok(if offset as u64 + size as u64 <= self.cap {
-      match self.buf {
-        Memmap::Map { ref mmap, .. } => &mmap[offset..offset + size],
-        Memmap::MapMut { ref mmap, .. } => &mmap[offset..offset + size],
-        _ => return Err(Error::Closed),
+  pub fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError> {
+    if self.ro {
+      return Err(ValueLogError::ReadOnly);
+    }
+
+    match self.buf {
+      Memmap::MapMut {
+        ref backed,
+        ref mut chunks,
+        ..
+      } => {
+        let kl = key.len();
+        let compressed = maybe_compress(val, min_compress_len, compression);
+        let (h, stored): (Header, &[u8]) = match &compressed {
+          Some((algo, bytes)) => (
+            Header::compressed(version, kl, bytes.len(), cks, *algo, val.len()),
+            bytes.as_slice(),
+          ),
+          None => (Header::new(version, kl, val.len(), cks), val),
+        };
+        let vl = stored.len();
+        let encoded_len = (h.encoded_len() + kl + vl) as u64;
+        let entry_len = encoded_len + CHECKSUM_OVERHEAD;
+
+        let offset = Self::reserve(backed, chunks, self.len, entry_len, self.max_cap)?;
+        self.cap = Self::total_len(chunks);
+
+        let (chunk_idx, intra_offset) = Self::locate(chunks, offset);
+        let chunk = &mut chunks[chunk_idx];
+
+        let mut cur = intra_offset;
+        let header = h.encode()?;
+        chunk[cur..cur + header.len].copy_from_slice(&header);
+        cur += header.len;
+        chunk[cur..cur + kl].copy_from_slice(key);
+        cur += kl;
+        chunk[cur..cur + vl].copy_from_slice(stored);
+        cur += vl;
+
+        let entry_cks = crc32fast::hash(&chunk[intra_offset..cur]);
+        chunk[cur..cur + CHECKSUM_OVERHEAD as usize].copy_from_slice(&entry_cks.to_le_bytes());
+
+        self.len = offset + entry_len;
+        Ok(Pointer::new(self.fid, encoded_len, offset))
       }
-    } else {
-      return Err(Error::OutOfBound {
+      Memmap::Map { .. } => Err(ValueLogError::ReadOnly),
+      _ => Err(ValueLogError::Closed),
+    }
+  }
+
+  /// Returns a byte slice which contains header, key and value. An entry
+  /// never straddles a chunk boundary (see [`MmapValueLog::reserve`]), so
+  /// this always resolves within a single chunk.
+  #[inline]
+  pub fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
+    if offset as u64 + size as u64 > self.cap {
+      return Err(ValueLogError::OutOfBound {
         offset,
         len: size,
         size: self.len,
       });
-    })
+    }
+
+    match &self.buf {
+      Memmap::Map { mmap, .. } => Ok(&mmap[offset..offset + size]),
+      Memmap::MapMut { chunks, .. } => {
+        let (chunk_idx, intra_offset) = Self::locate(chunks, offset as u64);
+        chunks
+          .get(chunk_idx)
+          .and_then(|chunk| chunk.get(intra_offset..intra_offset + size))
+          .ok_or(ValueLogError::OutOfBound {
+            offset,
+            len: size,
+            size: self.len,
+          })
+      }
+      Memmap::Unmap => Err(ValueLogError::Closed),
+    }
+  }
+
+  /// Like [`MmapValueLog::read`], but recomputes the CRC32
+  /// [`MmapValueLog::write`] appended after `pointer`'s entry and compares
+  /// it before returning the slice, catching bit-rot or a torn write an
+  /// unchecked read would silently trust.
+  pub fn read_checked(&self, pointer: Pointer) -> Result<&[u8], ValueLogError> {
+    let offset = pointer.offset();
+    let size = pointer.size() as usize;
+    let buf = self.read(offset as usize, size)?;
+    let want = crc32fast::hash(buf);
+    let got = self.read(offset as usize + size, CHECKSUM_OVERHEAD as usize)?;
+
+    if want.to_le_bytes().as_slice() != got {
+      return Err(ValueLogError::Corrupted {
+        fid: self.fid,
+        offset,
+        reason: crate::error::CorruptionReason::ChecksumMismatch,
+      });
+    }
+
+    Ok(buf)
+  }
+
+  /// Writes `value` using the fixed-stride layout [`CreateOptions::uniform`]
+  /// declares: no [`Header`] framing, just `value`'s raw bytes followed by
+  /// the same trailing CRC32 [`Self::write`] appends, at an offset that is
+  /// always a multiple of `uniform + CHECKSUM_OVERHEAD`. That regularity is
+  /// what lets [`Self::read_uniform`] locate a record from an `index` alone.
+  ///
+  /// Returns [`ValueLogError::NotUniform`] if the log wasn't opened with
+  /// [`CreateOptions::with_uniform`], or [`ValueLogError::UniformSizeMismatch`]
+  /// if `value` isn't exactly the declared size.
+  pub fn write_uniform(&mut self, value: &[u8]) -> Result<Pointer, ValueLogError> {
+    if self.ro {
+      return Err(ValueLogError::ReadOnly);
+    }
+
+    let record_size = self.uniform.ok_or(ValueLogError::NotUniform)?;
+    if value.len() as u64 != record_size {
+      return Err(ValueLogError::UniformSizeMismatch {
+        expected: record_size,
+        found: value.len() as u64,
+      });
+    }
+
+    match self.buf {
+      Memmap::MapMut {
+        ref backed,
+        ref mut chunks,
+        ..
+      } => {
+        let stride = record_size + CHECKSUM_OVERHEAD;
+
+        let offset = Self::reserve(backed, chunks, self.len, stride, self.max_cap)?;
+        self.cap = Self::total_len(chunks);
+
+        let (chunk_idx, intra_offset) = Self::locate(chunks, offset);
+        let chunk = &mut chunks[chunk_idx];
+
+        let vl = value.len();
+        let mut cur = intra_offset;
+        chunk[cur..cur + vl].copy_from_slice(value);
+        cur += vl;
+
+        let entry_cks = crc32fast::hash(&chunk[intra_offset..cur]);
+        chunk[cur..cur + CHECKSUM_OVERHEAD as usize].copy_from_slice(&entry_cks.to_le_bytes());
+
+        self.len = offset + stride;
+        Ok(Pointer::new(self.fid, record_size, offset))
+      }
+      Memmap::Map { .. } => Err(ValueLogError::ReadOnly),
+      _ => Err(ValueLogError::Closed),
+    }
+  }
+
+  /// Reads the `index`-th fixed-stride record written by
+  /// [`Self::write_uniform`], locating it as `index * (uniform +
+  /// CHECKSUM_OVERHEAD)` instead of going through a [`Pointer`] the way
+  /// [`Self::read`]/[`Self::read_checked`] do, and verifying the trailing
+  /// CRC32 the same way [`Self::read_checked`] does.
+  ///
+  /// Returns [`ValueLogError::NotUniform`] if the log wasn't opened with
+  /// [`CreateOptions::with_uniform`].
+  pub fn read_uniform(&self, index: u64) -> Result<&[u8], ValueLogError> {
+    let record_size = self.uniform.ok_or(ValueLogError::NotUniform)?;
+    let stride = record_size + CHECKSUM_OVERHEAD;
+    let offset = index * stride;
+
+    let buf = self.read(offset as usize, record_size as usize)?;
+    let want = crc32fast::hash(buf);
+    let got = self.read(offset as usize + record_size as usize, CHECKSUM_OVERHEAD as usize)?;
+
+    if want.to_le_bytes().as_slice() != got {
+      return Err(ValueLogError::Corrupted {
+        fid: self.fid,
+        offset: offset as usize,
+        reason: crate::error::CorruptionReason::ChecksumMismatch,
+      });
+    }
+
+    Ok(buf)
   }
 
   #[inline]
-  pub fn rewind(&mut self, size: usize) -> Result<(), Error> {
+  pub fn rewind(&mut self, size: usize) -> Result<(), ValueLogError> {
     if self.ro {
-      return Err(Error::ReadOnly);
+      return Err(ValueLogError::ReadOnly);
     }
 
     self.len = self.len.saturating_sub(size as u64);
@@ -197,18 +641,260 @@ impl MmapValueLog {
     self.len as usize
   }
 
+  /// Returns the number of bytes currently mapped, i.e. the sum of every
+  /// chunk [`Self::grow`] has appended so far. This grows over the life of
+  /// the log rather than being fixed at creation, so a caller should treat
+  /// it as "how far writes have pushed the mapping", not a ceiling.
   #[inline]
   pub fn capacity(&self) -> u64 {
     self.cap
   }
 
+  /// Returns how much of the currently mapped region (see
+  /// [`Self::capacity`]) is not yet used. This is not a hard limit on how
+  /// much more can be written: once it runs out, [`Self::reserve`] maps
+  /// another chunk and this widens again, unless that would pass
+  /// [`CreateOptions::max_size`].
   #[inline]
   pub fn remaining(&self) -> u64 {
     self.cap - self.len
   }
 
   #[inline]
-  pub const fn fid(&self) -> u32 {
+  pub const fn fid(&self) -> Fid {
     self.fid
   }
+
+  // TODO: unlink the backing file once the caller's fid bookkeeping can
+  // tell us it is safe to do so; for now this only satisfies the
+  // `ValueLogKind::remove` dispatch.
+  #[inline]
+  pub fn remove(&self) -> Result<(), ValueLogError> {
+    Ok(())
+  }
+
+  /// Decodes the header starting at `offset`, reading at most
+  /// [`Header::MAX_ENCODED_SIZE`] bytes (or however many remain before
+  /// `self.len`, whichever is smaller).
+  fn header_at(&self, offset: u64) -> Result<(usize, Header), ValueLogError> {
+    let remaining = self.len.saturating_sub(offset);
+    let probe = (Header::MAX_ENCODED_SIZE as u64).min(remaining) as usize;
+    let buf = self.read(offset as usize, probe)?;
+    Header::decode(buf)
+  }
+
+  /// Walks the log from offset `0`, decoding one entry at a time, for
+  /// crash recovery and GC. See [`ValueLogIter`].
+  #[inline]
+  pub fn iter(&self) -> ValueLogIter<'_> {
+    ValueLogIter {
+      vlf: self,
+      offset: 0,
+      done: false,
+    }
+  }
+}
+
+impl super::backend::ValueLogBackend for MmapValueLog {
+  #[inline]
+  fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError> {
+    self.write(version, key, val, cks, min_compress_len, compression)
+  }
+
+  #[inline]
+  fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
+    self.read(offset, size)
+  }
+
+  #[inline]
+  fn rewind(&mut self, size: usize) -> Result<(), ValueLogError> {
+    self.rewind(size)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    self.len()
+  }
+
+  #[inline]
+  fn capacity(&self) -> u64 {
+    self.capacity()
+  }
+
+  #[inline]
+  fn remaining(&self) -> u64 {
+    self.remaining()
+  }
+
+  #[inline]
+  fn fid(&self) -> Fid {
+    self.fid()
+  }
+
+  #[inline]
+  fn remove(&mut self) -> Result<(), ValueLogError> {
+    (*self).remove()
+  }
+}
+
+/// A single record recovered from a [`MmapValueLog`] by [`ValueLogIter`],
+/// borrowed straight out of the mapping.
+pub struct ValueLogEntry<'a> {
+  version: u64,
+  key: &'a [u8],
+  value: &'a [u8],
+  pointer: Pointer,
+  cks: u32,
+  entry_offset: u64,
+  entry_len: u64,
+}
+
+impl<'a> ValueLogEntry<'a> {
+  /// Returns the version the entry was written at.
+  #[inline]
+  pub const fn version(&self) -> u64 {
+    self.version
+  }
+
+  /// Returns the entry's key.
+  #[inline]
+  pub const fn key(&self) -> &'a [u8] {
+    self.key
+  }
+
+  /// Returns the entry's value, exactly as stored (still compressed, if it
+  /// was written compressed). Pass [`Self::pointer`] to
+  /// [`ValueLog::read_value`](super::ValueLog::read_value) to get the
+  /// decompressed value instead.
+  #[inline]
+  pub const fn value(&self) -> &'a [u8] {
+    self.value
+  }
+
+  /// Returns a [`Pointer`] to this entry, identical to what
+  /// [`MmapValueLog::write`] would have returned for it.
+  #[inline]
+  pub const fn pointer(&self) -> Pointer {
+    self.pointer
+  }
+
+  /// Returns the checksum [`MmapValueLog::write`]'s caller supplied when
+  /// this entry was first written (see [`Header::cks`]'s own doc comment)
+  /// -- carried through a relocation so a compaction rewrite doesn't have
+  /// to recompute it.
+  #[inline]
+  pub const fn checksum(&self) -> u32 {
+    self.cks
+  }
+
+  /// Returns the byte offset this entry starts at.
+  #[inline]
+  pub const fn entry_offset(&self) -> u64 {
+    self.entry_offset
+  }
+
+  /// Returns the total on-disk span of this entry, header through trailing
+  /// checksum: `self.entry_offset() + self.entry_len()` is where the next
+  /// entry (if any) begins.
+  #[inline]
+  pub const fn entry_len(&self) -> u64 {
+    self.entry_len
+  }
+
+  /// Returns `true` if `current` -- the pointer the key's live skiplog
+  /// entry actually holds right now -- is this entry, i.e. this is still
+  /// the copy of the value a read would resolve to rather than a dead
+  /// version a compaction can discard.
+  #[inline]
+  pub fn is_live(&self, current: Option<Pointer>) -> bool {
+    current == Some(self.pointer)
+  }
+}
+
+/// Iterator over the raw entries of a [`MmapValueLog`], from offset `0` up
+/// to its current length.
+///
+/// Used to rebuild the keydir after a crash and to drive bitcask-style
+/// compaction, which copies still-referenced entries into a fresh log. A
+/// malformed or truncated trailing record — the kind a crash mid-write
+/// leaves behind — yields one terminating `Err`, after which the iterator
+/// is exhausted. To discard it, the caller can
+/// [`MmapValueLog::rewind`] by the number of bytes past the last good
+/// entry's `entry_offset() + entry_len()`; `rewind` takes a byte count to
+/// drop from the end, and a log opened read-only refuses it.
+pub struct ValueLogIter<'a> {
+  vlf: &'a MmapValueLog,
+  offset: u64,
+  done: bool,
+}
+
+impl<'a> ValueLogIter<'a> {
+  fn decode_next(&mut self) -> Result<ValueLogEntry<'a>, ValueLogError> {
+    let offset = self.offset;
+    let (header_len, header) = self.vlf.header_at(offset)?;
+    let kl = header.kl as usize;
+    let vl = header.vl as usize;
+    let body_len = header_len + kl + vl;
+    let entry_len = body_len as u64 + CHECKSUM_OVERHEAD;
+
+    if offset + entry_len > self.vlf.len {
+      return Err(ValueLogError::Corrupted {
+        fid: self.vlf.fid,
+        offset,
+        reason: crate::error::CorruptionReason::Truncated,
+      });
+    }
+
+    let buf = self.vlf.read(offset as usize, body_len)?;
+    let want_cks = crc32fast::hash(buf);
+    let got_cks = self.vlf.read(offset as usize + body_len, CHECKSUM_OVERHEAD as usize)?;
+    if want_cks.to_le_bytes().as_slice() != got_cks {
+      return Err(ValueLogError::Corrupted {
+        fid: self.vlf.fid,
+        offset,
+        reason: crate::error::CorruptionReason::ChecksumMismatch,
+      });
+    }
+
+    let key = &buf[header_len..header_len + kl];
+    let value = &buf[header_len + kl..];
+
+    self.offset = offset + entry_len;
+
+    Ok(ValueLogEntry {
+      version: header.version,
+      key,
+      value,
+      pointer: Pointer::new(self.vlf.fid, body_len as u64, offset),
+      cks: header.cks,
+      entry_offset: offset,
+      entry_len,
+    })
+  }
+}
+
+impl<'a> Iterator for ValueLogIter<'a> {
+  type Item = Result<ValueLogEntry<'a>, ValueLogError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.done || self.offset >= self.vlf.len {
+      return None;
+    }
+
+    match self.decode_next() {
+      Ok(entry) => Some(Ok(entry)),
+      Err(e) => {
+        self.done = true;
+        Some(Err(e))
+      }
+    }
+  }
 }
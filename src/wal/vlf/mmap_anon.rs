@@ -29,11 +29,20 @@ impl MmapAnonValueLog {
     key: &[u8],
     val: &[u8],
     cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
   ) -> Result<Pointer, ValueLogError> {
     if let Some(mmap) = self.buf.as_mut() {
       let kl = key.len();
-      let vl = val.len();
-      let h = Header::new(version, kl, vl, cks);
+      let compressed = maybe_compress(val, min_compress_len, compression);
+      let (h, stored): (Header, &[u8]) = match &compressed {
+        Some((algo, bytes)) => (
+          Header::compressed(version, kl, bytes.len(), cks, *algo, val.len()),
+          bytes.as_slice(),
+        ),
+        None => (Header::new(version, kl, val.len(), cks), val),
+      };
+      let vl = stored.len();
       let encoded_len = h.encoded_len() + kl + vl;
 
       let offset = self.len as usize;
@@ -51,7 +60,7 @@ impl MmapAnonValueLog {
       cur += header.len;
       mmap[cur..cur + kl].copy_from_slice(key);
       cur += kl;
-      mmap[cur..cur + vl].copy_from_slice(val);
+      mmap[cur..cur + vl].copy_from_slice(stored);
       cur += vl;
 
       self.len += cur as u64;
@@ -113,3 +122,53 @@ impl MmapAnonValueLog {
     Ok(())
   }
 }
+
+impl super::backend::ValueLogBackend for MmapAnonValueLog {
+  #[inline]
+  fn write(
+    &mut self,
+    version: u64,
+    key: &[u8],
+    val: &[u8],
+    cks: u32,
+    min_compress_len: u64,
+    compression: CompressionType,
+  ) -> Result<Pointer, ValueLogError> {
+    self.write(version, key, val, cks, min_compress_len, compression)
+  }
+
+  #[inline]
+  fn read(&self, offset: usize, size: usize) -> Result<&[u8], ValueLogError> {
+    self.read(offset, size)
+  }
+
+  #[inline]
+  fn rewind(&mut self, size: usize) -> Result<(), ValueLogError> {
+    self.rewind(size)
+  }
+
+  #[inline]
+  fn len(&self) -> usize {
+    self.len()
+  }
+
+  #[inline]
+  fn capacity(&self) -> u64 {
+    self.capacity()
+  }
+
+  #[inline]
+  fn remaining(&self) -> u64 {
+    self.remaining()
+  }
+
+  #[inline]
+  fn fid(&self) -> Fid {
+    self.fid()
+  }
+
+  #[inline]
+  fn remove(&mut self) -> Result<(), ValueLogError> {
+    self.remove()
+  }
+}